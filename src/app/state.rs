@@ -1,4 +1,7 @@
-use crate::model::result::BenchResult;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+use std::time::Instant;
+use crate::model::result::{BenchResult, BenchScore, StressReport};
 
 pub enum AppState {
     Idle,
@@ -6,7 +9,19 @@ pub enum AppState {
         current_test: String,
         completed: usize,
         total: usize,
+        last: Option<BenchScore>,
+        warnings: Vec<String>,
     },
     Showing(BenchResult),
+    Stressing {
+        current_test: String,
+        started: Instant,
+        duration_secs: u64,
+        passes: usize,
+        warnings: Vec<String>,
+        /// Set to end the stress run after the current pass
+        stop: Arc<AtomicBool>,
+    },
+    StressShowing(StressReport),
     Error(String),
 }
\ No newline at end of file
@@ -1,82 +1,342 @@
 use egui::RichText;
+use egui_plot::{Legend, Line, Plot, PlotPoints};
 use crossbeam_channel::{unbounded, Receiver};
 use chrono::Local;
+use log::{Level, LevelFilter};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
 
 use crate::{
+    engines::noise::warnings,
+    engines::profile::{self, Profile},
+    engines::score::{changes, rescore, Rescored, ScoreChange},
+    engines::stress::{decay_percent, problems, run_stress},
+    engines::thermal::samples_in,
     engines::runner::{run_benchmarks, RunnerEvent},
-    benchmarks::{
-        cpu::{
-            CpuMultiCore,
-            CpuIntMath,
-            CpuFloatMath,
-            CpuPrimeCalc,
-            CpuSSE,
-            CpuCompression,
-            CpuEncryption,
-            CpuPhysics,
-            CpuSorting,
-            CpuUCT,
-        },
-        memory::{
-            MemoryDBOps,
-            MemoryCachedRead,
-            MemoryUncachedRead,
-            MemoryWrite,
-            MemoryAvailable,
-            MemoryLatency,
-            MemoryThreaded,
-        },
-        disk::{DiskSequentialRead, DiskSequentialWrite, DiskRandomIOPS32K, DiskRandomIOPS4K},
-    },
-    util::sysinfo::get_system_info,
+    benchmarks::{catalog, default_suite, stress_suite},
     app::state::AppState,
+    export::{render, system_lines, with_interval, Format},
+    model::result::{Aggregation, BenchResult, BenchScore, Direction, ExecMode, Execution, RunSettings, StressConfig, StressReport, ThermalSample, ThermalTrace},
+    util::logging::{self, LogLine},
+    util::numa::parse_cpulist,
 };
 
+// small values such as energy drift would round to 0.00
+fn format_metric(value: f64) -> String {
+    if value != 0.0 && value.abs() < 0.01 {
+        format!("{:.2e}", value)
+    } else {
+        format!("{:.2}", value)
+    }
+}
+
+// the report and the log of the run that produced it, side by side
+fn export(prefix: &str, json: String, log_start: u64) {
+    let stem = format!("{}_{}", prefix, Local::now().timestamp());
+    std::fs::write(format!("{}.json", stem), json).ok();
+    std::fs::write(format!("{}.log", stem), logging::text_since(log_start)).ok();
+}
+
+// le résultat dans le format choisi, à l'endroit choisi, et le log du run à côté
+fn export_result(result: &BenchResult, format: Format, log_start: u64) {
+    let name = format!("bench_{}.{}", Local::now().timestamp(), format.extension());
+    let Some(path) = rfd::FileDialog::new().set_file_name(name).add_filter(format.label(), &[format.extension()]).save_file() else {
+        return;
+    };
+    let written = std::fs::write(&path, render(result, format))
+        .and_then(|()| std::fs::write(path.with_extension("log"), logging::text_since(log_start)));
+    match written {
+        Ok(()) => log::info!("result exported to {}", path.display()),
+        Err(e) => log::error!("cannot export to {}: {}", path.display(), e),
+    }
+}
+
+fn format_execution(exec: &Execution) -> String {
+    match exec.mode {
+        ExecMode::FixedTime { secs } => format!("[fixed time {}s, batch {}, {} units]", secs, exec.batch, exec.units),
+        ExecMode::FixedWork { units } => format!("[fixed work {} units]", units),
+    }
+}
+
+// fréquence et températures pendant un benchmark, pour voir le throttling
+fn thermal_chart(ui: &mut egui::Ui, trace: &ThermalTrace, score: &BenchScore) {
+    let Some(span) = score.span else { return };
+    let samples = samples_in(trace, span);
+    if samples.is_empty() {
+        return;
+    }
+    let mean_freq = |s: &ThermalSample| s.freq_mhz.iter().sum::<u64>() as f64 / s.freq_mhz.len().max(1) as f64;
+    let (low, high) = samples.iter().map(mean_freq).fold((f64::MAX, 0.0f64), |(lo, hi), f| (lo.min(f), hi.max(f)));
+    let peak = samples.iter().flat_map(|s| s.temps_c.iter().flatten().copied()).reduce(f32::max);
+    let mut title = format!("Fréquence moyenne {:.0}–{:.0} MHz", low, high);
+    if let Some(peak) = peak {
+        title.push_str(&format!("  ·  max {:.0} °C", peak));
+    }
+
+    egui::CollapsingHeader::new(title).id_source(("thermal", &score.name)).show(ui, |ui| {
+        thermal_plots(ui, &score.name, trace, samples, span.start_secs);
+    });
+}
+
+// x = secondes depuis `origin`
+fn thermal_plots(ui: &mut egui::Ui, id: &str, trace: &ThermalTrace, samples: &[ThermalSample], origin: f64) {
+    let x = |s: &ThermalSample| s.t_secs - origin;
+    if let Some(first) = samples.first() {
+        Plot::new(("freq", id))
+            .height(120.0)
+            .y_axis_label("MHz")
+            .allow_drag(false)
+            .allow_zoom(false)
+            .allow_scroll(false)
+            .show(ui, |plot| {
+                for core in 0..first.freq_mhz.len() {
+                    let points: PlotPoints = samples.iter().filter_map(|s| s.freq_mhz.get(core).map(|&f| [x(s), f as f64])).collect();
+                    plot.line(Line::new(points).name(format!("cpu{}", core)));
+                }
+            });
+    }
+    if !trace.sensors.is_empty() {
+        Plot::new(("temp", id))
+            .height(120.0)
+            .y_axis_label("°C")
+            .legend(Legend::default())
+            .allow_drag(false)
+            .allow_zoom(false)
+            .allow_scroll(false)
+            .show(ui, |plot| {
+                for (i, sensor) in trace.sensors.iter().enumerate() {
+                    let points: PlotPoints = samples
+                        .iter()
+                        .filter_map(|s| s.temps_c.get(i).copied().flatten().map(|t| [x(s), t as f64]))
+                        .collect();
+                    plot.line(Line::new(points).name(sensor));
+                }
+            });
+    }
+}
+
+// verdict, problèmes, débit de chaque workload au fil du temps
+fn stress_report(ui: &mut egui::Ui, report: &StressReport) {
+    let problems = problems(report);
+    let score_size = (ui.available_width() / 15.0).clamp(20.0, 48.0);
+    if problems.is_empty() {
+        ui.label(RichText::new("Stable").size(score_size).strong().color(egui::Color32::GREEN));
+    } else {
+        ui.label(RichText::new("Unstable").size(score_size).strong().color(egui::Color32::RED));
+    }
+    let mut summary = format!("{:.0} min of {} min", report.elapsed_secs / 60.0, report.config.duration_secs / 60);
+    if report.stopped {
+        summary.push_str(" (stopped)");
+    }
+    ui.label(summary);
+    for problem in &problems {
+        ui.colored_label(egui::Color32::RED, problem);
+    }
+    ui.separator();
+
+    for workload in &report.workloads {
+        let mut line = format!("{}: {} passes, {} errors", workload.name, workload.iterations.len(), workload.errors());
+        if let Some(decay) = decay_percent(workload) {
+            line.push_str(&format!("  ·  throughput change {:+.1}%", -decay));
+        }
+        ui.label(RichText::new(line).strong());
+        if workload.iterations.is_empty() {
+            continue;
+        }
+        Plot::new(("stress", &workload.name))
+            .height(120.0)
+            .y_axis_label(&workload.unit)
+            .allow_drag(false)
+            .allow_zoom(false)
+            .allow_scroll(false)
+            .show(ui, |plot| {
+                let points: PlotPoints = workload.iterations.iter().map(|i| [i.t_secs, i.value as f64]).collect();
+                plot.line(Line::new(points).name(&workload.name));
+            });
+    }
+
+    egui::CollapsingHeader::new("Fréquence et température").id_source("stress thermal").show(ui, |ui| {
+        thermal_plots(ui, "stress", &report.thermal, &report.thermal.samples, 0.0);
+    });
+}
+
+// profil de référence et agrégation, au démarrage et pour re-scorer
+fn scoring_options(ui: &mut egui::Ui, profiles: &[Profile], selected: &mut usize, aggregation: &mut Aggregation) {
+    egui::ComboBox::from_label("Reference")
+        .selected_text(&profiles[*selected].id)
+        .show_ui(ui, |ui| {
+            for (i, profile) in profiles.iter().enumerate() {
+                ui.selectable_value(selected, i, &profile.id).on_hover_text(&profile.machine);
+            }
+        });
+    egui::ComboBox::from_label("Aggregation")
+        .selected_text(format!("{:?}", aggregation))
+        .show_ui(ui, |ui| {
+            ui.selectable_value(aggregation, Aggregation::Geometric, "Geometric");
+            ui.selectable_value(aggregation, Aggregation::Arithmetic, "Arithmetic");
+        });
+}
+
 pub struct OBenchmarkApp {
     state: AppState,
     receiver: Option<Receiver<RunnerEvent>>,
+    // options du runner
+    pin_cores: bool,
+    cores_input: String,
+    high_priority: bool,
+    // profils de référence : celui livré puis ceux de l'utilisateur
+    profiles: Vec<Profile>,
+    profile: usize,
+    aggregation: Aggregation,
+    /// Score differences from the last re-scoring, shown above the result
+    changes: Vec<ScoreChange>,
+    // panneau de logs
+    show_log: bool,
+    log_level: LevelFilter,
+    /// First log line of the current run, saved with its export
+    log_start: u64,
+    export_format: Format,
+    /// Result the shown one is compared with
+    baseline: Option<BenchResult>,
+    comparison: Vec<ScoreChange>,
+    /// Why the comparison was re-scored, or why there is none
+    comparison_notes: Vec<String>,
+    /// Benchmarks the last re-scoring left unscored, and why
+    skipped: Vec<String>,
+    // options du mode stress
+    stress: StressConfig,
+    stress_minutes: u64,
 }
 
 impl OBenchmarkApp {
     pub fn new(cc: &eframe::CreationContext<'_>) -> Self {
         crate::app::theme::apply_ui_theme(&cc.egui_ctx);
 
-        Self { state: AppState::Idle, receiver: None }
+        let stress = StressConfig::default();
+        Self {
+            state: AppState::Idle,
+            receiver: None,
+            pin_cores: false,
+            cores_input: "0".to_string(),
+            high_priority: false,
+            profiles: profile::available(),
+            profile: 0,
+            aggregation: Aggregation::default(),
+            changes: Vec::new(),
+            show_log: false,
+            log_level: LevelFilter::Info,
+            log_start: 0,
+            export_format: Format::default(),
+            baseline: None,
+            comparison: Vec::new(),
+            comparison_notes: Vec::new(),
+            skipped: Vec::new(),
+            stress_minutes: stress.duration_secs / 60,
+            stress,
+        }
+    }
+
+    fn rescore(&mut self, result: &BenchResult) {
+        let Rescored { result: rescored, skipped } = rescore(result, &catalog(), &self.profiles[self.profile], self.aggregation);
+        self.changes = changes(result, &rescored);
+        self.skipped = skipped;
+        self.compare(&rescored);
+        self.state = AppState::Showing(rescored);
+    }
+
+    // la base est re-scorée comme le résultat si besoin, sinon les scores ne se comparent pas
+    fn compare(&mut self, result: &BenchResult) {
+        self.comparison.clear();
+        self.comparison_notes.clear();
+        let Some(baseline) = &self.baseline else { return };
+        let baseline = if baseline.profile == result.profile && baseline.aggregation == result.aggregation {
+            baseline.clone()
+        } else if let Some(profile) = self.profiles.iter().find(|p| p.id == result.profile) {
+            self.comparison_notes.push(format!(
+                "Baseline scored against profile {}, re-scored against {} with a {:?} mean",
+                baseline.profile, profile.id, result.aggregation
+            ));
+            let rescored = rescore(baseline, &catalog(), profile, result.aggregation);
+            self.comparison_notes.extend(rescored.skipped.into_iter().map(|reason| format!("{}, not scored", reason)));
+            rescored.result
+        } else {
+            self.comparison_notes.push(format!("Profile {} is not available, the baseline (profile {}) cannot be re-scored to compare", result.profile, baseline.profile));
+            return;
+        };
+        self.comparison = changes(&baseline, result);
+    }
+
+    // a result saved by the JSON export, shown as it was recorded
+    fn open_result(&mut self) {
+        let Some(path) = rfd::FileDialog::new().add_filter("JSON", &["json"]).pick_file() else {
+            return;
+        };
+        match BenchResult::load(&path) {
+            Ok(result) => {
+                log::info!("opened {}", path.display());
+                self.changes.clear();
+                self.skipped.clear();
+                self.log_start = logging::next_seq();
+                self.compare(&result);
+                self.state = AppState::Showing(result);
+            }
+            Err(e) => self.state = AppState::Error(format!("{:#}", e)),
+        }
+    }
+
+    // any result saved by the JSON export, picked with a file dialog
+    fn rescore_file(&mut self) {
+        let Some(path) = rfd::FileDialog::new().add_filter("JSON", &["json"]).pick_file() else {
+            return;
+        };
+        match BenchResult::load(&path) {
+            Ok(result) => self.rescore(&result),
+            Err(e) => self.state = AppState::Error(format!("{:#}", e)),
+        }
+    }
+
+    fn run_settings(&self) -> anyhow::Result<RunSettings> {
+        let cores = if self.pin_cores { parse_cpulist(&self.cores_input)? } else { Vec::new() };
+        Ok(RunSettings { cores, high_priority: self.high_priority, priority_raised: false })
     }
 }
 
 impl eframe::App for OBenchmarkApp {
     fn update(&mut self, ctx: &egui::Context, _: &mut eframe::Frame) {
         let mut should_restart = false;
+        let mut rescore_shown = false;
+        let mut rescore_file = false;
+        let mut go_idle = false;
+        let mut open_result = false;
+        // the shown result or the baseline changed
+        let mut compare_shown = false;
 
         if let Some(rx) = &self.receiver {
             while let Ok(event) = rx.try_recv() {
                 match event {
-                    RunnerEvent::BenchStarted(name) => {
-                        let (completed, total) = match &self.state {
-                            AppState::Running { completed, total, .. } => (*completed, *total),
-                            _ => (0, 0),
-                        };
-                        self.state = AppState::Running {
-                            current_test: name.clone(),
-                            completed,
-                            total,
-                        };
-                    }
-                    RunnerEvent::BenchFinished(_, _) => {
-                        if let AppState::Running { completed, .. } = &self.state {
-                            let new_completed = completed + 1;
-                            if let AppState::Running { current_test, total, .. } = &self.state {
-                                self.state = AppState::Running {
-                                    current_test: current_test.clone(),
-                                    completed: new_completed,
-                                    total: *total,
-                                };
-                            }
+                    RunnerEvent::BenchStarted(name) => match &mut self.state {
+                        AppState::Running { current_test, .. } | AppState::Stressing { current_test, .. } => *current_test = name,
+                        _ => {}
+                    },
+                    RunnerEvent::BenchFinished(score) => match &mut self.state {
+                        AppState::Running { completed, last, .. } => {
+                            *completed += 1;
+                            *last = Some(score);
                         }
-                    }
+                        AppState::Stressing { passes, .. } => *passes += 1,
+                        _ => {}
+                    },
+                    RunnerEvent::Warning(warning) => match &mut self.state {
+                        AppState::Running { warnings, .. } | AppState::Stressing { warnings, .. } => warnings.push(warning),
+                        _ => {}
+                    },
                     RunnerEvent::Done(result) => {
                         self.state = AppState::Showing(result.clone());
+                        compare_shown = true;
+                    }
+                    RunnerEvent::StressDone(report) => {
+                        self.state = AppState::StressShowing(report);
                     }
                     RunnerEvent::Error(e) => {
                         self.state = AppState::Error(e);
@@ -85,54 +345,160 @@ impl eframe::App for OBenchmarkApp {
             }
         }
 
+        egui::TopBottomPanel::bottom("log").resizable(true).show_animated(ctx, self.show_log, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(RichText::new("Log").strong());
+                egui::ComboBox::from_id_source("log level")
+                    .selected_text(self.log_level.as_str())
+                    .show_ui(ui, |ui| {
+                        for level in [LevelFilter::Error, LevelFilter::Warn, LevelFilter::Info, LevelFilter::Debug] {
+                            ui.selectable_value(&mut self.log_level, level, level.as_str());
+                        }
+                    });
+            });
+            logging::with_lines(|lines| {
+                let shown: Vec<&LogLine> = lines.iter().filter(|l| l.level <= self.log_level).collect();
+                let row_height = ui.text_style_height(&egui::TextStyle::Monospace);
+                egui::ScrollArea::vertical().stick_to_bottom(true).auto_shrink([false, false]).show_rows(ui, row_height, shown.len(), |ui, rows| {
+                    for line in &shown[rows] {
+                        let color = match line.level {
+                            Level::Error => egui::Color32::RED,
+                            Level::Warn => egui::Color32::YELLOW,
+                            Level::Info => ui.visuals().text_color(),
+                            _ => egui::Color32::GRAY,
+                        };
+                        ui.label(RichText::new(&line.text).monospace().color(color));
+                    }
+                });
+            });
+        });
+
         egui::CentralPanel::default().show(ctx, |ui| {
             let avail = ui.available_width();
             // responsive heading size
             let heading_size = (avail / 20.0).clamp(20.0, 60.0);
-            ui.heading(RichText::new("OBenchmark").size(heading_size));
+            ui.horizontal(|ui| {
+                ui.heading(RichText::new("OBenchmark").size(heading_size));
+                ui.toggle_value(&mut self.show_log, "📜 Log");
+            });
             ui.separator();
 
             match &self.state {
                 AppState::Idle => {
                     ui.vertical_centered(|ui| {
+                        ui.horizontal(|ui| {
+                            ui.checkbox(&mut self.pin_cores, "Pin to cores");
+                            ui.add_enabled(
+                                self.pin_cores,
+                                egui::TextEdit::singleline(&mut self.cores_input).hint_text("0-3,8").desired_width(120.0),
+                            );
+                            ui.checkbox(&mut self.high_priority, "High priority");
+                            scoring_options(ui, &self.profiles, &mut self.profile, &mut self.aggregation);
+                        });
+                        ui.horizontal(|ui| {
+                            if ui.button("Open result…").clicked() {
+                                open_result = true;
+                            }
+                            if ui.button("Rescore result file…").clicked() {
+                                rescore_file = true;
+                            }
+                        });
                         if ui.add_sized([avail * 0.5, 40.0], egui::Button::new("Start Benchmark")).clicked() {
-                            let (tx, rx) = unbounded();
-
-                            // build vector of benchmarks so we can know total count
-                            let benches: Vec<Box<dyn crate::engines::benchmark::Benchmark>> = vec![
-                            Box::new(CpuMultiCore),
-                            Box::new(CpuIntMath),
-                            Box::new(CpuFloatMath),
-                            Box::new(CpuPrimeCalc),
-                            Box::new(CpuSSE),
-                            Box::new(CpuCompression),
-                            Box::new(CpuEncryption),
-                            Box::new(CpuPhysics),
-                            Box::new(CpuSorting),
-                            Box::new(CpuUCT),
-                            Box::new(MemoryDBOps),
-                            Box::new(MemoryCachedRead),
-                            Box::new(MemoryUncachedRead),
-                            Box::new(MemoryWrite),
-                            Box::new(MemoryAvailable),
-                            Box::new(MemoryLatency),
-                            Box::new(MemoryThreaded),
-                            Box::new(DiskSequentialRead),
-                            Box::new(DiskSequentialWrite),
-                            Box::new(DiskRandomIOPS32K),
-                            Box::new(DiskRandomIOPS4K),
-                        ];
-                        let total = benches.len();
-                        self.state = AppState::Running { current_test: String::new(), completed: 0, total };
-
-                        run_benchmarks(benches, tx);
-                        self.receiver = Some(rx);
+                            match self.run_settings() {
+                                Ok(settings) => {
+                                    let (tx, rx) = unbounded();
+
+                                    // build vector of benchmarks so we can know total count
+                                    let benches = default_suite();
+                                    let total = benches.len();
+                                    self.changes.clear();
+                                    self.skipped.clear();
+                                    self.log_start = logging::next_seq();
+                                    self.state = AppState::Running { current_test: String::new(), completed: 0, total, last: None, warnings: Vec::new() };
+
+                                    run_benchmarks(benches, settings, self.profiles[self.profile].clone(), self.aggregation, tx);
+                                    self.receiver = Some(rx);
+                                }
+                                Err(e) => self.state = AppState::Error(format!("{:#}", e)),
+                            }
+                        }
+
+                        ui.separator();
+                        ui.horizontal(|ui| {
+                            ui.label("Stress:");
+                            ui.checkbox(&mut self.stress.cpu, "CPU");
+                            ui.checkbox(&mut self.stress.memory, "Memory");
+                            ui.checkbox(&mut self.stress.disk, "Disk");
+                            ui.add(egui::DragValue::new(&mut self.stress_minutes).clamp_range(1..=24 * 60).suffix(" min"));
+                        });
+                        let any = self.stress.cpu || self.stress.memory || self.stress.disk;
+                        if ui.add_enabled(any, egui::Button::new("Start Stress Test").min_size(egui::vec2(avail * 0.5, 40.0))).clicked() {
+                            match self.run_settings() {
+                                Ok(settings) => {
+                                    let (tx, rx) = unbounded();
+                                    let config = StressConfig { duration_secs: self.stress_minutes * 60, ..self.stress.clone() };
+                                    let stop = Arc::new(AtomicBool::new(false));
+                                    self.log_start = logging::next_seq();
+                                    self.state = AppState::Stressing {
+                                        current_test: String::new(),
+                                        started: Instant::now(),
+                                        duration_secs: config.duration_secs,
+                                        passes: 0,
+                                        warnings: Vec::new(),
+                                        stop: stop.clone(),
+                                    };
+
+                                    run_stress(stress_suite(&config), config, settings, stop, tx);
+                                    self.receiver = Some(rx);
+                                }
+                                Err(e) => self.state = AppState::Error(format!("{:#}", e)),
+                            }
                         }
                     });
                 }
 
-                AppState::Running { current_test, completed, total } => {
+                AppState::Stressing { current_test, started, duration_secs, passes, warnings, stop } => {
+                    ui.label(RichText::new(format!("Stress en cours: {}", current_test)).size(18.0).strong());
+                    ui.label(format!("Passes: {}  ·  erreurs: {}", passes, warnings.len()));
+                    for warning in warnings {
+                        ui.colored_label(egui::Color32::RED, format!("⚠ {}", warning));
+                    }
+                    ui.separator();
+
+                    let elapsed = started.elapsed().as_secs();
+                    let progress = (elapsed as f32 / *duration_secs as f32).min(1.0);
+                    ui.label(format!("Temps: {} / {} min", elapsed / 60, duration_secs / 60));
+                    ui.add(egui::ProgressBar::new(progress).desired_width(avail).show_percentage());
+                    if stop.load(Ordering::Relaxed) {
+                        ui.label("Arrêt après la passe en cours...");
+                    } else if ui.button("⏹ Stop").clicked() {
+                        stop.store(true, Ordering::Relaxed);
+                    }
+                }
+
+                AppState::StressShowing(report) => {
+                    egui::ScrollArea::vertical().auto_shrink([false, false]).show(ui, |ui| {
+                        stress_report(ui, report);
+                        ui.separator();
+                        ui.horizontal(|ui| {
+                            if ui.button("Export Report JSON").clicked() {
+                                export("stress", serde_json::to_string_pretty(&report).unwrap(), self.log_start);
+                            }
+                            if ui.button("⬅ Back").clicked() {
+                                go_idle = true;
+                            }
+                        });
+                    });
+                }
+
+                AppState::Running { current_test, completed, total, last, warnings } => {
                     ui.label(RichText::new(format!("Test en cours: {}", current_test)).size(18.0).strong());
+                    if let Some(last) = last {
+                        ui.label(format!("Dernier résultat: {} = {} {}", last.name, last.raw_score, last.unit));
+                    }
+                    for warning in warnings {
+                        ui.colored_label(egui::Color32::YELLOW, format!("⚠ {}", warning));
+                    }
                     ui.separator();
                     
                     // Barre de progression du test actuel
@@ -151,77 +517,174 @@ impl eframe::App for OBenchmarkApp {
                     egui::ScrollArea::vertical().auto_shrink([false, false]).show(ui, |ui| {
                         // responsive final score text
                         let score_size = (avail / 15.0).clamp(20.0, 48.0);
-                        ui.label(RichText::new(format!("Score final: {}", result.final_score)).size(score_size).strong());
+                        ui.label(RichText::new(format!("Score final: {}", with_interval(result.final_score, result.final_interval))).size(score_size).strong());
+                        if !result.sub_scores.is_empty() {
+                            let subs: Vec<String> = result
+                                .sub_scores
+                                .iter()
+                                .map(|s| format!("{} {} ({} tests)", s.category.label(), with_interval(s.score, s.interval), s.count))
+                                .collect();
+                            ui.label(RichText::new(subs.join("  ·  ")).size(18.0).strong());
+                        }
+                        ui.label(format!("{:?} mean", result.aggregation));
+                        match self.profiles.iter().find(|p| p.id == result.profile) {
+                            Some(profile) => ui.label(format!("Profile {}: 1000 = {}", profile.id, profile.machine)),
+                            None => ui.label(format!("Profile {}", result.profile)),
+                        };
+                        if !self.changes.is_empty() {
+                            egui::CollapsingHeader::new("Changes after re-scoring").default_open(true).show(ui, |ui| {
+                                for change in &self.changes {
+                                    ui.label(change.describe());
+                                }
+                                for reason in &self.skipped {
+                                    ui.colored_label(egui::Color32::YELLOW, format!("{}, not scored", reason));
+                                }
+                            });
+                        }
+                        if let Some(baseline) = &self.baseline {
+                            egui::CollapsingHeader::new("Compared with baseline").default_open(true).show(ui, |ui| {
+                                let machine = baseline.system.as_ref().map_or("unknown machine", |s| s.cpu.as_str());
+                                let date = if baseline.date.is_empty() { "undated" } else { baseline.date.as_str() };
+                                ui.label(format!("Baseline: {}  ·  {}  ·  score {}", machine, date, baseline.final_score));
+                                for note in &self.comparison_notes {
+                                    ui.colored_label(egui::Color32::YELLOW, note);
+                                }
+                                for change in &self.comparison {
+                                    ui.label(change.describe());
+                                }
+                            });
+                        }
+                        if result.noisy {
+                            ui.colored_label(egui::Color32::YELLOW, "⚠ Noisy environment: other processes loaded the machine during the run");
+                            for sample in &result.load {
+                                for warning in warnings(sample) {
+                                    ui.colored_label(egui::Color32::YELLOW, warning);
+                                }
+                            }
+                        }
                         ui.separator();
 
                         ui.label(RichText::new("Détail des scores:").size(18.0).strong());
-                        // use columns for score detail to adapt width
-                        ui.columns(2, |cols| {
-                            for score in &result.scores {
+                        for score in &result.scores {
+                            // use columns for score detail to adapt width
+                            ui.columns(2, |cols| {
                                 cols[0].label(&score.name);
-                                cols[1].label(RichText::new(format!("{}", score.raw_score)).strong());
-                            }
-                        });
+                                let mut value = format!("{} {}", score.raw_score, score.unit);
+                                if score.direction == Direction::LowerIsBetter {
+                                    value.push_str(" (lower is better)");
+                                }
+                                for m in &score.metrics {
+                                    value.push_str(&format!("  ·  {} {}{}", m.name, format_metric(m.value), m.unit));
+                                }
+                                if let Some(exec) = &score.execution {
+                                    value.push_str(&format!("  {}", format_execution(exec)));
+                                }
+                                if score.valid {
+                                    cols[1].label(RichText::new(value).strong());
+                                } else {
+                                    cols[1].label(RichText::new(format!("{}  ·  invalide (vérification échouée)", value)).strong().color(egui::Color32::RED));
+                                }
+                            });
+                            thermal_chart(ui, &result.thermal, score);
+                        }
 
                         ui.separator();
                         ui.label("System Info");
 
-                        let sys = get_system_info();
-
-                        ui.label(format!("CPU: {}", sys.global_cpu_info().brand()));
-                        ui.label(format!("Cores: {}", sys.cpus().len()));
-                        ui.label(format!("RAM: {} MB", sys.total_memory() / 1024));
-                        ui.label(format!("OS: {:?}", sysinfo::System::name()));
+                        for (label, value) in system_lines(result) {
+                            ui.label(format!("{}: {}", label, value));
+                        }
 
                         ui.horizontal(|ui| {
-                            if ui.button("Export Result JSON").clicked() {
-                                let json = serde_json::to_string_pretty(&result).unwrap();
-                                std::fs::write(format!("bench_{}.json", Local::now().timestamp()), json).ok();
+                            egui::ComboBox::from_id_source("export format")
+                                .selected_text(self.export_format.label())
+                                .show_ui(ui, |ui| {
+                                    for format in Format::ALL {
+                                        ui.selectable_value(&mut self.export_format, format, format.label());
+                                    }
+                                });
+                            if ui.button("Export…").clicked() {
+                                export_result(result, self.export_format, self.log_start);
                             }
 
                             if ui.button("🔄 New Analysis").clicked() {
                                 should_restart = true;
                             }
                         });
+                        ui.horizontal(|ui| {
+                            scoring_options(ui, &self.profiles, &mut self.profile, &mut self.aggregation);
+                            if ui.button("Rescore").clicked() {
+                                rescore_shown = true;
+                            }
+                            if ui.button("Rescore file…").clicked() {
+                                rescore_file = true;
+                            }
+                        });
+                        ui.horizontal(|ui| {
+                            if ui.button("Open result…").clicked() {
+                                open_result = true;
+                            }
+                            if ui.button("Use as baseline").clicked() {
+                                self.baseline = Some(result.clone());
+                                compare_shown = true;
+                            }
+                            if self.baseline.is_some() && ui.button("Clear baseline").clicked() {
+                                self.baseline = None;
+                                compare_shown = true;
+                            }
+                        });
                     });
                 }
 
                 AppState::Error(err) => {
                     ui.colored_label(egui::Color32::RED, err);
+                    if ui.button("Back").clicked() {
+                        go_idle = true;
+                    }
                 }
             }
         });
 
+        if go_idle {
+            self.state = AppState::Idle;
+            self.receiver = None;
+        }
+
+        if rescore_shown {
+            if let AppState::Showing(result) = &self.state {
+                let result = result.clone();
+                self.rescore(&result);
+            }
+        }
+        if rescore_file {
+            self.rescore_file();
+        }
+        if open_result {
+            self.open_result();
+        }
+        if compare_shown {
+            if let AppState::Showing(result) = &self.state {
+                let result = result.clone();
+                self.compare(&result);
+            }
+        }
+
         if should_restart {
-            let (tx, rx) = unbounded();
-            let benches: Vec<Box<dyn crate::engines::benchmark::Benchmark>> = vec![
-                Box::new(CpuMultiCore),
-                Box::new(CpuIntMath),
-                Box::new(CpuFloatMath),
-                Box::new(CpuPrimeCalc),
-                Box::new(CpuSSE),
-                Box::new(CpuCompression),
-                Box::new(CpuEncryption),
-                Box::new(CpuPhysics),
-                Box::new(CpuSorting),
-                Box::new(CpuUCT),
-                Box::new(MemoryDBOps),
-                Box::new(MemoryCachedRead),
-                Box::new(MemoryUncachedRead),
-                Box::new(MemoryWrite),
-                Box::new(MemoryAvailable),
-                Box::new(MemoryLatency),
-                Box::new(MemoryThreaded),
-                Box::new(DiskSequentialRead),
-                Box::new(DiskSequentialWrite),
-                Box::new(DiskRandomIOPS32K),
-                Box::new(DiskRandomIOPS4K),
-            ];
-            let total = benches.len();
-            self.state = AppState::Running { current_test: String::new(), completed: 0, total };
-
-            run_benchmarks(benches, tx);
-            self.receiver = Some(rx);
+            match self.run_settings() {
+                Ok(settings) => {
+                    self.changes.clear();
+                    self.skipped.clear();
+                    self.log_start = logging::next_seq();
+                    let (tx, rx) = unbounded();
+                    let benches = default_suite();
+                    let total = benches.len();
+                    self.state = AppState::Running { current_test: String::new(), completed: 0, total, last: None, warnings: Vec::new() };
+
+                    run_benchmarks(benches, settings, self.profiles[self.profile].clone(), self.aggregation, tx);
+                    self.receiver = Some(rx);
+                }
+                Err(e) => self.state = AppState::Error(format!("{:#}", e)),
+            }
         }
 
         ctx.request_repaint_after(std::time::Duration::from_millis(16));
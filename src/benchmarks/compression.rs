@@ -0,0 +1,208 @@
+use std::hint::black_box;
+use std::io::{Read, Write};
+use std::sync::Arc;
+use anyhow::Result;
+use flate2::{Compression, read::ZlibDecoder, write::ZlibEncoder};
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use crate::engines::benchmark::{Benchmark, BenchOutput};
+use crate::engines::timing::measure;
+use crate::model::result::{Category, ExecMode};
+
+const CORPUS_SIZE: usize = 4 * 1024 * 1024; // 4 MB per corpus
+
+/// A compression algorithm that can be benchmarked at several levels.
+/// Add an implementation and list it in `suite()` to benchmark a new codec.
+pub trait Codec: Send + Sync {
+    fn name(&self) -> &str;
+    fn levels(&self) -> &[u32];
+    fn compress(&self, data: &[u8], level: u32) -> Result<Vec<u8>>;
+    fn decompress(&self, data: &[u8], original_len: usize) -> Result<Vec<u8>>;
+}
+
+pub struct Zlib;
+impl Codec for Zlib {
+    fn name(&self) -> &str { "zlib" }
+    fn levels(&self) -> &[u32] { &[1, 6, 9] }
+
+    fn compress(&self, data: &[u8], level: u32) -> Result<Vec<u8>> {
+        let mut encoder = ZlibEncoder::new(Vec::with_capacity(data.len() / 2), Compression::new(level));
+        encoder.write_all(data)?;
+        Ok(encoder.finish()?)
+    }
+
+    fn decompress(&self, data: &[u8], original_len: usize) -> Result<Vec<u8>> {
+        let mut out = Vec::with_capacity(original_len);
+        ZlibDecoder::new(data).read_to_end(&mut out)?;
+        Ok(out)
+    }
+}
+
+// Corpus généré de façon déterministe (même contenu à chaque run)
+pub struct Corpus {
+    pub name: &'static str,
+    pub data: Vec<u8>,
+}
+
+pub fn corpora() -> Vec<Corpus> {
+    vec![
+        Corpus { name: "text", data: text_corpus(CORPUS_SIZE, 1) },
+        Corpus { name: "binary", data: binary_corpus(CORPUS_SIZE, 2) },
+        Corpus { name: "compressed", data: random_corpus(CORPUS_SIZE, 3) },
+    ]
+}
+
+// English-like prose: skewed word frequencies, punctuation and line breaks
+fn text_corpus(size: usize, seed: u64) -> Vec<u8> {
+    const WORDS: &[&str] = &[
+        "the", "of", "and", "to", "in", "a", "is", "that", "for", "it", "as", "was", "with", "be",
+        "by", "on", "not", "he", "this", "are", "or", "his", "from", "at", "which", "but", "have",
+        "an", "had", "they", "you", "were", "their", "one", "all", "we", "can", "her", "has",
+        "there", "been", "if", "more", "when", "will", "would", "who", "so", "no", "benchmark",
+        "memory", "processor", "result", "score", "thread", "system", "measure", "throughput",
+        "latency", "compression", "performance", "hardware", "software", "reference",
+    ];
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut out = Vec::with_capacity(size + 32);
+    let mut words_in_line = 0;
+    let mut sentence_start = true;
+    while out.len() < size {
+        // cubic skew so common words dominate, as in natural language
+        let idx = (rng.gen::<f64>().powi(3) * WORDS.len() as f64) as usize;
+        let word = WORDS[idx.min(WORDS.len() - 1)].as_bytes();
+        if sentence_start {
+            out.push(word[0].to_ascii_uppercase());
+            out.extend_from_slice(&word[1..]);
+            sentence_start = false;
+        } else {
+            out.extend_from_slice(word);
+        }
+        words_in_line += 1;
+        if rng.gen_ratio(1, 12) {
+            out.push(if rng.gen_ratio(1, 5) { b'?' } else { b'.' });
+            sentence_start = true;
+        } else if rng.gen_ratio(1, 15) {
+            out.push(b',');
+        }
+        if words_in_line >= 14 {
+            out.push(b'\n');
+            words_in_line = 0;
+        } else {
+            out.push(b' ');
+        }
+    }
+    out.truncate(size);
+    out
+}
+
+// Fixed-size records as found in logs or telemetry dumps
+fn binary_corpus(size: usize, seed: u64) -> Vec<u8> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut out = Vec::with_capacity(size + 16);
+    let mut id: u32 = 0;
+    let mut timestamp: u32 = 1_700_000_000;
+    let mut value: f32 = 100.0;
+    while out.len() < size {
+        id += 1;
+        timestamp += rng.gen_range(0..4);
+        value += rng.gen_range(-0.5..0.5);
+        let flags: u16 = [0x0001, 0x0003, 0x0010, 0x8000][rng.gen_range(0..4)];
+        out.extend_from_slice(&id.to_le_bytes());
+        out.extend_from_slice(&timestamp.to_le_bytes());
+        out.extend_from_slice(&value.to_le_bytes());
+        out.extend_from_slice(&flags.to_le_bytes());
+        out.extend_from_slice(&rng.gen::<u16>().to_le_bytes());
+    }
+    out.truncate(size);
+    out
+}
+
+// High-entropy bytes, behaves like already-compressed data (JPEG, zip...)
+fn random_corpus(size: usize, seed: u64) -> Vec<u8> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut out = vec![0u8; size];
+    rng.fill(&mut out[..]);
+    out
+}
+
+pub struct CompressionBench {
+    name: String,
+    codec: Arc<dyn Codec>,
+    level: u32,
+    decompress: bool,
+}
+
+impl CompressionBench {
+    pub fn new(codec: Arc<dyn Codec>, level: u32, decompress: bool) -> Self {
+        let pass = if decompress { "Decompress" } else { "Compress" };
+        let name = format!("CPU {} {} L{}", pass, codec.name(), level);
+        Self { name, codec, level, decompress }
+    }
+}
+
+impl Benchmark for CompressionBench {
+    fn name(&self) -> &str { &self.name }
+    fn weight(&self) -> u64 { 1 }
+    fn category(&self) -> Category { Category::Cpu }
+    // zlib -9 runs at a few MB/s: whole MB/s would make the score move in big steps
+    fn unit(&self) -> &str { "KB/s" }
+    fn mode(&self) -> ExecMode { ExecMode::FixedTime { secs: 3.0 } }
+
+    fn run(&self) -> Result<BenchOutput> {
+        let corpora = corpora();
+        let compressed = corpora
+            .iter()
+            .map(|c| self.codec.compress(&c.data, self.level))
+            .collect::<Result<Vec<_>>>()?;
+
+        // one work unit = every corpus (de)compressed once; throughput is in uncompressed bytes
+        let exec = measure(self.mode(), |n| {
+            for _ in 0..n {
+                for (corpus, packed) in corpora.iter().zip(&compressed) {
+                    if self.decompress {
+                        black_box(self.codec.decompress(black_box(packed), corpus.data.len())?);
+                    } else {
+                        black_box(self.codec.compress(black_box(&corpus.data), self.level)?);
+                    }
+                }
+            }
+            Ok(())
+        })?;
+
+        // round-trip check: count the corpora that do not decompress to their original bytes
+        let mut mismatches: u64 = 0;
+        for (corpus, packed) in corpora.iter().zip(&compressed) {
+            if self.codec.decompress(packed, corpus.data.len())? != corpus.data {
+                mismatches += 1;
+            }
+        }
+
+        let set_kb = corpora.iter().map(|c| c.data.len()).sum::<usize>() as f64 / 1024.0;
+        let kb_s = exec.units_per_sec() * set_kb;
+        let mut output = BenchOutput::from(kb_s.round() as u64).with_checksum(mismatches).with_execution(exec);
+        let mut total_in = 0usize;
+        let mut total_out = 0usize;
+        for (corpus, packed) in corpora.iter().zip(&compressed) {
+            total_in += corpus.data.len();
+            total_out += packed.len();
+            let ratio = corpus.data.len() as f64 / packed.len() as f64;
+            output = output.with_metric(format!("ratio {}", corpus.name), ratio, "x");
+        }
+        Ok(output.with_metric("ratio", total_in as f64 / total_out as f64, "x"))
+    }
+
+    fn expected_checksum(&self) -> Option<u64> { Some(0) }
+}
+
+/// Compress and decompress benchmarks for every codec and level
+pub fn suite() -> Vec<Box<dyn Benchmark>> {
+    let codecs: Vec<Arc<dyn Codec>> = vec![Arc::new(Zlib)];
+    let mut benches: Vec<Box<dyn Benchmark>> = Vec::new();
+    for codec in codecs {
+        for &level in codec.levels() {
+            benches.push(Box::new(CompressionBench::new(codec.clone(), level, false)));
+            benches.push(Box::new(CompressionBench::new(codec.clone(), level, true)));
+        }
+    }
+    benches
+}
@@ -1,33 +1,40 @@
 use rayon::prelude::*;
-use std::time::Instant;
-use std::io::Write;
+use std::hint::black_box;
 use anyhow::Result;
-use crate::engines::benchmark::Benchmark;
-use flate2::{Compression, write::ZlibEncoder};
-use sha2::{Sha256, Digest};
-use rand::{Rng, SeedableRng};
-use rand::rngs::StdRng;
+use crate::engines::benchmark::{Benchmark, BenchOutput};
+use crate::model::result::Category;
+use crate::engines::timing::measure;
+
+// Réponses connues pour la vérification des résultats
+const MULTI_CORE_BATCH_SUM: u64 = 0xf3a2_04b4_b822_f7a0;
+const INT_MATH_BATCH_RESULT: u64 = 0xf18f_0193_e341_7781;
+const FLOAT_MATH_BATCH_RESULT: f64 = 0.768_169_100_989_599_7;
 
 // multi-core generic loop
 pub struct CpuMultiCore;
 impl Benchmark for CpuMultiCore {
     fn name(&self) -> &str { "CPU Multi-Core" }
     fn weight(&self) -> u64 { 3 }
+    fn category(&self) -> Category { Category::Cpu }
+    fn unit(&self) -> &str { "ops/s" }
 
-    fn run(&self) -> Result<u64> {
-        let start = Instant::now();
-        let duration_secs = 5;
-        let mut iterations: u64 = 0;
-        while start.elapsed().as_secs() < duration_secs {
-            let batch: u64 = (0..1_000_000)
-                .into_par_iter()
-                .map(|i: u64| i.wrapping_mul(6364136223846793005).wrapping_add(1))
-                .count() as u64;
-            iterations += batch;
-        }
-        let elapsed = start.elapsed().as_secs_f64();
-        Ok((iterations as f64 / elapsed) as u64)
+    fn run(&self) -> Result<BenchOutput> {
+        // one work unit = one parallel pass over 1M values
+        let mut batch_sum = 0;
+        let exec = measure(self.mode(), |n| {
+            for _ in 0..n {
+                batch_sum = (0..1_000_000u64)
+                    .into_par_iter()
+                    .map(|i: u64| black_box(i.wrapping_mul(6364136223846793005).wrapping_add(1)))
+                    .reduce(|| 0, u64::wrapping_add);
+            }
+            Ok(())
+        })?;
+        let ops = exec.units_per_sec() * 1_000_000.0;
+        Ok(BenchOutput::from(ops as u64).with_checksum(batch_sum).with_execution(exec))
     }
+
+    fn expected_checksum(&self) -> Option<u64> { Some(MULTI_CORE_BATCH_SUM) }
 }
 
 // 1. math entier
@@ -35,15 +42,27 @@ pub struct CpuIntMath;
 impl Benchmark for CpuIntMath {
     fn name(&self) -> &str { "CPU Int Math" }
     fn weight(&self) -> u64 { 2 }
-    fn run(&self) -> Result<u64> {
-        let start = Instant::now();
+    fn category(&self) -> Category { Category::Cpu }
+    fn unit(&self) -> &str { "ops/s" }
+    fn run(&self) -> Result<BenchOutput> {
+        const ROUND: u64 = 100_000;
         let mut x: u64 = 1;
-        while start.elapsed().as_secs() < 5 {
-            x = x.wrapping_mul(123456789).wrapping_add(987654321);
-            x = x.wrapping_sub(54321);
-        }
-        Ok(x)
+        let exec = measure(self.mode(), |n| {
+            for _ in 0..n {
+                // every round starts from the same seed so its result is a known answer
+                x = 1;
+                for _ in 0..ROUND {
+                    x = black_box(x).wrapping_mul(123456789).wrapping_add(987654321);
+                    x = x.wrapping_sub(54321);
+                }
+            }
+            Ok(())
+        })?;
+        let ops = exec.units_per_sec() * ROUND as f64;
+        Ok(BenchOutput::from(ops as u64).with_checksum(x).with_execution(exec))
     }
+
+    fn expected_checksum(&self) -> Option<u64> { Some(INT_MATH_BATCH_RESULT) }
 }
 
 // 2. math flottante
@@ -51,39 +70,28 @@ pub struct CpuFloatMath;
 impl Benchmark for CpuFloatMath {
     fn name(&self) -> &str { "CPU Float Math" }
     fn weight(&self) -> u64 { 2 }
-    fn run(&self) -> Result<u64> {
-        let start = Instant::now();
+    fn category(&self) -> Category { Category::Cpu }
+    fn unit(&self) -> &str { "ops/s" }
+    fn run(&self) -> Result<BenchOutput> {
+        const ROUND: u64 = 10_000;
         let mut x: f64 = 1.0;
-        while start.elapsed().as_secs() < 5 {
-            x = x * 1.0000001 + 0.0000001;
-            x = x.sin().cos();
-        }
-        Ok(x as u64)
-    }
-}
-
-// 3. calcul des nombres premiers
-pub struct CpuPrimeCalc;
-impl Benchmark for CpuPrimeCalc {
-    fn name(&self) -> &str { "CPU Prime Calc" }
-    fn weight(&self) -> u64 { 2 }
-    fn run(&self) -> Result<u64> {
-        let mut count = 0;
-        let start = Instant::now();
-        let mut n = 2;
-        while start.elapsed().as_secs() < 5 {
-            let mut is_prime = true;
-            for i in 2..((n as f64).sqrt() as u64 + 1) {
-                if n % i == 0 {
-                    is_prime = false;
-                    break;
+        let exec = measure(self.mode(), |n| {
+            for _ in 0..n {
+                x = 1.0;
+                for _ in 0..ROUND {
+                    x = black_box(x) * 1.0000001 + 0.0000001;
+                    x = x.sin().cos();
                 }
             }
-            if is_prime { count += 1; }
-            n += 1;
-        }
-        Ok(count)
+            Ok(())
+        })?;
+        // libm implementations differ in the last bits, compare with a tolerance
+        let errors = ((x - FLOAT_MATH_BATCH_RESULT).abs() > 1e-9) as u64;
+        let ops = exec.units_per_sec() * ROUND as f64;
+        Ok(BenchOutput::from(ops as u64).with_checksum(errors).with_execution(exec))
     }
+
+    fn expected_checksum(&self) -> Option<u64> { Some(0) }
 }
 
 // 4. instructions étendues (simulated via vector ops)
@@ -91,99 +99,29 @@ pub struct CpuSSE;
 impl Benchmark for CpuSSE {
     fn name(&self) -> &str { "CPU SSE Ext" }
     fn weight(&self) -> u64 { 2 }
-    fn run(&self) -> Result<u64> {
+    fn category(&self) -> Category { Category::Cpu }
+    fn unit(&self) -> &str { "ops/s" }
+    fn run(&self) -> Result<BenchOutput> {
         let mut a = vec![1f32; 1_000_000];
-        let mut b = vec![2f32; 1_000_000];
-        let start = Instant::now();
-        while start.elapsed().as_secs() < 5 {
-            for i in 0..a.len() {
-                a[i] = a[i] + b[i];
-            }
-        }
-        Ok(0)
-    }
-}
-
-// 5. compression
-pub struct CpuCompression;
-impl Benchmark for CpuCompression {
-    fn name(&self) -> &str { "CPU Compression" }
-    fn weight(&self) -> u64 { 2 }
-    fn run(&self) -> Result<u64> {
-        let data = vec![0u8; 10_000_000];
-        let start = Instant::now();
-        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
-        encoder.write_all(&data)?;
-        let _ = encoder.finish()?;
-        let elapsed = start.elapsed().as_secs_f64();
-        Ok((1.0 / elapsed) as u64)
-    }
-}
-
-// 6. cryptage (SHA256 loop)
-pub struct CpuEncryption;
-impl Benchmark for CpuEncryption {
-    fn name(&self) -> &str { "CPU Encryption" }
-    fn weight(&self) -> u64 { 2 }
-    fn run(&self) -> Result<u64> {
-        let mut hasher = Sha256::new();
-        let data = vec![0u8; 1024 * 1024];
-        let start = Instant::now();
-        while start.elapsed().as_secs() < 5 {
-            hasher.update(&data);
-            let _ = hasher.finalize_reset();
-        }
-        Ok(0)
-    }
-}
-
-// 7. simulation physique simple
-pub struct CpuPhysics;
-impl Benchmark for CpuPhysics {
-    fn name(&self) -> &str { "CPU Physics" }
-    fn weight(&self) -> u64 { 2 }
-    fn run(&self) -> Result<u64> {
-        let mut pos = vec![0f64; 100000];
-        let vel = vec![1f64; 100000];
-        let start = Instant::now();
-        while start.elapsed().as_secs() < 5 {
-            for i in 0..pos.len() {
-                pos[i] += vel[i];
+        let b = vec![2f32; 1_000_000];
+        // one work unit = one pass over the arrays; calibration passes count too
+        let mut passes: u32 = 0;
+        let exec = measure(self.mode(), |n| {
+            for _ in 0..n {
+                for (x, y) in a.iter_mut().zip(&b) {
+                    *x += *y;
+                }
+                black_box(&mut a);
+                passes += 1;
             }
-        }
-        Ok(0)
+            Ok(())
+        })?;
+        // 1 + 2 * passes is exact in f32 for any realistic number of passes
+        let expected = 1.0 + 2.0 * passes as f32;
+        let errors = a.iter().filter(|&&x| x != expected).count() as u64;
+        let ops = exec.units_per_sec() * a.len() as f64;
+        Ok(BenchOutput::from(ops as u64).with_checksum(errors).with_execution(exec))
     }
-}
 
-// 8. tri
-pub struct CpuSorting;
-impl Benchmark for CpuSorting {
-    fn name(&self) -> &str { "CPU Sorting" }
-    fn weight(&self) -> u64 { 2 }
-    fn run(&self) -> Result<u64> {
-        let mut rng = StdRng::seed_from_u64(123);
-        let mut v: Vec<u64> = (0..1_000_000).map(|_| rng.gen()).collect();
-        let start = Instant::now();
-        v.sort();
-        let elapsed = start.elapsed().as_secs_f64();
-        Ok((1.0 / elapsed) as u64)
-    }
+    fn expected_checksum(&self) -> Option<u64> { Some(0) }
 }
-
-// 9. uct single thread (dummy loop)
-pub struct CpuUCT;
-impl Benchmark for CpuUCT {
-    fn name(&self) -> &str { "CPU UCT Single" }
-    fn weight(&self) -> u64 { 2 }
-    fn run(&self) -> Result<u64> {
-        let start = Instant::now();
-        let mut count = 0;
-        while start.elapsed().as_secs() < 5 {
-            // simulate tree search
-            for _ in 0..10000 {
-                count += 1;
-            }
-        }
-        Ok(count)
-    }
-}
\ No newline at end of file
@@ -0,0 +1,174 @@
+use std::hint::black_box;
+use anyhow::{anyhow, Result};
+use aes_gcm::Aes256Gcm;
+use aes_gcm::aead::{AeadInPlace, KeyInit, Nonce, Tag};
+use chacha20poly1305::ChaCha20Poly1305;
+use ed25519_dalek::{Signer, SigningKey};
+use sha2::{Digest, Sha256, Sha512};
+use sha2::digest::FixedOutputReset;
+use crate::engines::benchmark::{Benchmark, BenchOutput};
+use crate::engines::timing::measure;
+use crate::model::result::{Category, ExecMode};
+
+const RUN_MODE: ExecMode = ExecMode::FixedTime { secs: 3.0 };
+const BUFFER_SIZE: usize = 1024 * 1024; // 1 MB par itération
+
+// Known answers over `test_buffer()`: first 8 bytes (little endian) of the
+// digest, and of the tag produced by the first encryption (key 0x42.., nonce 1);
+// the later encryptions are checked by decrypting them back
+const SHA256_DIGEST: u64 = 0x82d5_0a73_e001_81a8;
+const SHA512_DIGEST: u64 = 0x46ed_a099_1a20_e5fb;
+const AES_GCM_TAG: u64 = 0x40a6_2fdf_b98f_da53;
+const CHACHA20_POLY1305_TAG: u64 = 0x25aa_7576_0d4c_1b63;
+
+fn prefix(bytes: &[u8]) -> u64 {
+    let mut first = [0u8; 8];
+    first.copy_from_slice(&bytes[..8]);
+    u64::from_le_bytes(first)
+}
+
+fn test_buffer() -> Vec<u8> {
+    (0..BUFFER_SIZE).map(|i| (i.wrapping_mul(31) ^ (i >> 7)) as u8).collect()
+}
+
+// one work unit = one 1 MB buffer
+fn hash_throughput<D: Digest + FixedOutputReset>(mode: ExecMode) -> Result<BenchOutput> {
+    let data = test_buffer();
+    let mut hasher = D::new();
+    let mut digest = 0;
+    let exec = measure(mode, |n| {
+        for _ in 0..n {
+            Digest::update(&mut hasher, black_box(&data));
+            digest = prefix(&black_box(hasher.finalize_reset()));
+        }
+        Ok(())
+    })?;
+    Ok(BenchOutput::from(exec.units_per_sec() as u64).with_checksum(digest).with_execution(exec))
+}
+
+fn aead_nonce<A: AeadInPlace>(counter: u64) -> Nonce<A> {
+    let mut nonce = Nonce::<A>::default();
+    nonce[..8].copy_from_slice(&counter.to_le_bytes());
+    nonce
+}
+
+// one work unit = the buffer encrypted in place once more. Every pass is checked after
+// timing: decrypting back through all the nonces must verify each tag and end on the plaintext
+fn aead_throughput<A: AeadInPlace + KeyInit>(mode: ExecMode) -> Result<BenchOutput> {
+    let cipher = A::new_from_slice(&[0x42; 32]).map_err(|_| anyhow!("invalid key length"))?;
+    let mut buffer = test_buffer();
+    let mut tags: Vec<Tag<A>> = Vec::new();
+    let exec = measure(mode, |n| {
+        for _ in 0..n {
+            // never reuse a nonce with the same key, even in a benchmark
+            let nonce = aead_nonce::<A>(tags.len() as u64 + 1);
+            let tag = cipher
+                .encrypt_in_place_detached(&nonce, b"obenchmark", black_box(&mut buffer))
+                .map_err(|_| anyhow!("AEAD encryption failed"))?;
+            tags.push(black_box(tag));
+        }
+        Ok(())
+    })?;
+
+    let mut failures: u64 = 0;
+    for (i, tag) in tags.iter().enumerate().rev() {
+        if cipher.decrypt_in_place_detached(&aead_nonce::<A>(i as u64 + 1), b"obenchmark", &mut buffer, tag).is_err() {
+            failures += 1;
+        }
+    }
+    if buffer != test_buffer() {
+        failures += 1;
+    }
+    // the known tag of the first pass, shifted by any pass that did not check out
+    let first_tag = tags.first().map_or(0, |t| prefix(t));
+    Ok(BenchOutput::from(exec.units_per_sec() as u64).with_checksum(first_tag.wrapping_add(failures)).with_execution(exec))
+}
+
+// 1. hachage SHA-256
+pub struct CpuSha256;
+impl Benchmark for CpuSha256 {
+    fn name(&self) -> &str { "CPU SHA-256" }
+    fn weight(&self) -> u64 { 1 }
+    fn category(&self) -> Category { Category::Cpu }
+    fn unit(&self) -> &str { "MB/s" }
+    fn mode(&self) -> ExecMode { RUN_MODE }
+    fn run(&self) -> Result<BenchOutput> {
+        hash_throughput::<Sha256>(self.mode())
+    }
+
+    fn expected_checksum(&self) -> Option<u64> { Some(SHA256_DIGEST) }
+}
+
+// 2. hachage SHA-512
+pub struct CpuSha512;
+impl Benchmark for CpuSha512 {
+    fn name(&self) -> &str { "CPU SHA-512" }
+    fn weight(&self) -> u64 { 1 }
+    fn category(&self) -> Category { Category::Cpu }
+    fn unit(&self) -> &str { "MB/s" }
+    fn mode(&self) -> ExecMode { RUN_MODE }
+    fn run(&self) -> Result<BenchOutput> {
+        hash_throughput::<Sha512>(self.mode())
+    }
+
+    fn expected_checksum(&self) -> Option<u64> { Some(SHA512_DIGEST) }
+}
+
+// 3. chiffrement authentifié AES-256-GCM
+pub struct CpuAesGcm;
+impl Benchmark for CpuAesGcm {
+    fn name(&self) -> &str { "CPU AES-256-GCM" }
+    fn weight(&self) -> u64 { 1 }
+    fn category(&self) -> Category { Category::Cpu }
+    fn unit(&self) -> &str { "MB/s" }
+    fn mode(&self) -> ExecMode { RUN_MODE }
+    fn run(&self) -> Result<BenchOutput> {
+        aead_throughput::<Aes256Gcm>(self.mode())
+    }
+
+    fn expected_checksum(&self) -> Option<u64> { Some(AES_GCM_TAG) }
+}
+
+// 4. chiffrement authentifié ChaCha20-Poly1305
+pub struct CpuChaCha20Poly1305;
+impl Benchmark for CpuChaCha20Poly1305 {
+    fn name(&self) -> &str { "CPU ChaCha20-Poly1305" }
+    fn weight(&self) -> u64 { 1 }
+    fn category(&self) -> Category { Category::Cpu }
+    fn unit(&self) -> &str { "MB/s" }
+    fn mode(&self) -> ExecMode { RUN_MODE }
+    fn run(&self) -> Result<BenchOutput> {
+        aead_throughput::<ChaCha20Poly1305>(self.mode())
+    }
+
+    fn expected_checksum(&self) -> Option<u64> { Some(CHACHA20_POLY1305_TAG) }
+}
+
+// 5. signature à clé publique Ed25519
+pub struct CpuEd25519Sign;
+impl Benchmark for CpuEd25519Sign {
+    fn name(&self) -> &str { "CPU Ed25519 Sign" }
+    fn weight(&self) -> u64 { 1 }
+    fn category(&self) -> Category { Category::Cpu }
+    fn unit(&self) -> &str { "sig/s" }
+    fn mode(&self) -> ExecMode { RUN_MODE }
+    fn run(&self) -> Result<BenchOutput> {
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        let mut message = [0u8; 64];
+        let mut signatures: u64 = 0;
+        let mut last = key.sign(&message);
+        let exec = measure(self.mode(), |n| {
+            for _ in 0..n {
+                message[..8].copy_from_slice(&signatures.to_le_bytes());
+                last = black_box(key.sign(black_box(&message)));
+                signatures += 1;
+            }
+            Ok(())
+        })?;
+        // the last signature must verify against the public key
+        let failures = key.verify(&message, &last).is_err() as u64;
+        Ok(BenchOutput::from(exec.units_per_sec() as u64).with_checksum(failures).with_execution(exec))
+    }
+
+    fn expected_checksum(&self) -> Option<u64> { Some(0) }
+}
@@ -1,8 +1,12 @@
 use std::fs::{File, OpenOptions};
 use std::io::{Write, Read, Seek, SeekFrom};
-use std::time::{Instant, SystemTime};
+use std::time::SystemTime;
 use anyhow::Result;
-use crate::engines::benchmark::Benchmark;
+use crate::engines::benchmark::{Benchmark, BenchOutput};
+use crate::engines::timing::measure;
+use crate::model::result::{Category, ExecMode};
+
+const MB: usize = 1024 * 1024;
 
 // Helper pour générer un pseudo-aléatoire simple
 fn simple_random(seed: u64) -> u64 {
@@ -19,9 +23,12 @@ pub struct DiskSequentialRead;
 impl Benchmark for DiskSequentialRead {
     fn name(&self) -> &str { "Disk Seq Read" }
     fn weight(&self) -> u64 { 2 }
+    fn category(&self) -> Category { Category::Disk }
+    fn unit(&self) -> &str { "MB/s" }
+    fn mode(&self) -> ExecMode { ExecMode::FixedWork { units: 512 } }
 
-    fn run(&self) -> Result<u64> {
-        let size = 512 * 1024 * 1024; // 512 MB
+    fn run(&self) -> Result<BenchOutput> {
+        let size = 512 * MB; // 512 MB
         let data = vec![1u8; size];
 
         // Écrire le fichier
@@ -30,18 +37,33 @@ impl Benchmark for DiskSequentialRead {
         file.sync_all()?;
         drop(file);
 
-        // Tester la lecture
-        let start = Instant::now();
+        // Tester la lecture, one work unit = one 1 MB chunk; past the end it starts over
+        // from the first chunk, so any number of calls works (fixed time too)
         let mut file = File::open("benchmark_seq_read.dat")?;
         let mut buffer = vec![0u8; size];
-        file.read_exact(&mut buffer)?;
-        let elapsed = start.elapsed().as_secs_f64();
+        let chunks = size / MB;
+        let mut done = 0usize;
+        let exec = measure(self.mode(), |n| {
+            for _ in 0..n {
+                let chunk = done % chunks;
+                if chunk == 0 && done > 0 {
+                    file.seek(SeekFrom::Start(0))?;
+                }
+                file.read_exact(&mut buffer[chunk * MB..(chunk + 1) * MB])?;
+                done += 1;
+            }
+            Ok(())
+        })?;
 
         std::fs::remove_file("benchmark_seq_read.dat")?;
 
-        let speed_mb_s = (size as f64 / (1024.0 * 1024.0)) / elapsed;
-        Ok(speed_mb_s as u64)
+        // only the chunks actually read
+        let read = done.min(chunks) * MB;
+        let errors = (buffer[..read] != data[..read]) as u64;
+        Ok(BenchOutput::from(exec.units_per_sec() as u64).with_checksum(errors).with_execution(exec))
     }
+
+    fn expected_checksum(&self) -> Option<u64> { Some(0) }
 }
 
 // Test 2: Écriture séquentielle
@@ -50,22 +72,42 @@ pub struct DiskSequentialWrite;
 impl Benchmark for DiskSequentialWrite {
     fn name(&self) -> &str { "Disk Seq Write" }
     fn weight(&self) -> u64 { 2 }
+    fn category(&self) -> Category { Category::Disk }
+    fn unit(&self) -> &str { "MB/s" }
+    fn mode(&self) -> ExecMode { ExecMode::FixedWork { units: 512 } }
 
-    fn run(&self) -> Result<u64> {
-        let size = 512 * 1024 * 1024; // 512 MB
+    fn run(&self) -> Result<BenchOutput> {
+        let size = 512 * MB; // 512 MB
         let data = vec![1u8; size];
 
-        let start = Instant::now();
+        // one work unit = one 1 MB chunk, the sync ending each call is part of the measurement;
+        // past 512 MB it overwrites the file from the start, so any number of calls works
         let mut file = File::create("benchmark_seq_write.dat")?;
-        file.write_all(&data)?;
-        file.sync_all()?;
-        let elapsed = start.elapsed().as_secs_f64();
+        let chunks = size / MB;
+        let mut done = 0usize;
+        let exec = measure(self.mode(), |n| {
+            for _ in 0..n {
+                let chunk = done % chunks;
+                if chunk == 0 && done > 0 {
+                    file.seek(SeekFrom::Start(0))?;
+                }
+                file.write_all(&data[chunk * MB..(chunk + 1) * MB])?;
+                done += 1;
+            }
+            file.sync_all()?;
+            Ok(())
+        })?;
+        drop(file);
 
+        // Relire pour vérifier ce qui a été écrit
+        let written = std::fs::read("benchmark_seq_write.dat")?;
         std::fs::remove_file("benchmark_seq_write.dat")?;
 
-        let speed_mb_s = (size as f64 / (1024.0 * 1024.0)) / elapsed;
-        Ok(speed_mb_s as u64)
+        let errors = (written[..] != data[..done.min(chunks) * MB]) as u64;
+        Ok(BenchOutput::from(exec.units_per_sec() as u64).with_checksum(errors).with_execution(exec))
     }
+
+    fn expected_checksum(&self) -> Option<u64> { Some(0) }
 }
 
 // Test 3: IOPS 32K QD20
@@ -74,43 +116,53 @@ pub struct DiskRandomIOPS32K;
 impl Benchmark for DiskRandomIOPS32K {
     fn name(&self) -> &str { "Disk IOPS 32K QD20" }
     fn weight(&self) -> u64 { 2 }
+    fn category(&self) -> Category { Category::Disk }
+    fn unit(&self) -> &str { "IOPS" }
+    fn mode(&self) -> ExecMode { ExecMode::FixedWork { units: 10_000 } }
 
-    fn run(&self) -> Result<u64> {
+    fn run(&self) -> Result<BenchOutput> {
         let block_size = 32 * 1024; // 32 KB
         let _queue_depth = 20;
-        let total_ops = 10000;
         let file_size = 1024 * 1024 * 1024; // 1 GB
 
         // Créer le fichier de test
-        let mut file = OpenOptions::new()
+        let file = OpenOptions::new()
             .write(true)
             .create(true)
+            .truncate(true)
             .open("benchmark_iops_32k.dat")?;
         file.set_len(file_size as u64)?;
         drop(file);
 
         // Simuler les IOPS avec queue depth
-        let start = Instant::now();
         let mut file = OpenOptions::new()
             .read(true)
             .write(true)
             .open("benchmark_iops_32k.dat")?;
 
-        for i in 0..total_ops {
-            let offset = (simple_random(i as u64) % (file_size as u64 - block_size as u64)) as u64;
-            file.seek(SeekFrom::Start(offset))?;
-            
-            let mut buffer = vec![0u8; block_size];
-            let _ = file.read(&mut buffer);
-        }
-        file.sync_all()?;
-        let elapsed = start.elapsed().as_secs_f64();
+        // le fichier est creux : toute lecture doit être complète et ne contenir que des zéros
+        // one work unit = one random read
+        let mut errors: u64 = 0;
+        let exec = measure(self.mode(), |n| {
+            for i in 0..n {
+                let offset = simple_random(i) % (file_size as u64 - block_size as u64);
+                file.seek(SeekFrom::Start(offset))?;
+
+                let mut buffer = vec![0u8; block_size];
+                if file.read_exact(&mut buffer).is_err() || buffer.iter().any(|&b| b != 0) {
+                    errors += 1;
+                }
+            }
+            file.sync_all()?;
+            Ok(())
+        })?;
 
         std::fs::remove_file("benchmark_iops_32k.dat")?;
 
-        let iops = (total_ops as f64 / elapsed) as u64;
-        Ok(iops)
+        Ok(BenchOutput::from(exec.units_per_sec() as u64).with_checksum(errors).with_execution(exec))
     }
+
+    fn expected_checksum(&self) -> Option<u64> { Some(0) }
 }
 
 // Test 4: IOPS 4K QD1
@@ -119,40 +171,50 @@ pub struct DiskRandomIOPS4K;
 impl Benchmark for DiskRandomIOPS4K {
     fn name(&self) -> &str { "Disk IOPS 4K QD1" }
     fn weight(&self) -> u64 { 2 }
+    fn category(&self) -> Category { Category::Disk }
+    fn unit(&self) -> &str { "IOPS" }
+    fn mode(&self) -> ExecMode { ExecMode::FixedWork { units: 10_000 } }
 
-    fn run(&self) -> Result<u64> {
+    fn run(&self) -> Result<BenchOutput> {
         let block_size = 4 * 1024; // 4 KB
-        let total_ops = 10000;
         let file_size = 1024 * 1024 * 1024; // 1 GB
 
         // Créer le fichier de test
-        let mut file = OpenOptions::new()
+        let file = OpenOptions::new()
             .write(true)
             .create(true)
+            .truncate(true)
             .open("benchmark_iops_4k.dat")?;
         file.set_len(file_size as u64)?;
         drop(file);
 
         // Simuler les IOPS QD1 (une opération à la fois)
-        let start = Instant::now();
         let mut file = OpenOptions::new()
             .read(true)
             .write(true)
             .open("benchmark_iops_4k.dat")?;
 
-        for i in 0..total_ops {
-            let offset = (simple_random(i as u64) % (file_size as u64 - block_size as u64)) as u64;
-            file.seek(SeekFrom::Start(offset))?;
-            
-            let mut buffer = vec![0u8; block_size];
-            let _ = file.read(&mut buffer);
-        }
-        file.sync_all()?;
-        let elapsed = start.elapsed().as_secs_f64();
+        // le fichier est creux : toute lecture doit être complète et ne contenir que des zéros
+        // one work unit = one random read
+        let mut errors: u64 = 0;
+        let exec = measure(self.mode(), |n| {
+            for i in 0..n {
+                let offset = simple_random(i) % (file_size as u64 - block_size as u64);
+                file.seek(SeekFrom::Start(offset))?;
+
+                let mut buffer = vec![0u8; block_size];
+                if file.read_exact(&mut buffer).is_err() || buffer.iter().any(|&b| b != 0) {
+                    errors += 1;
+                }
+            }
+            file.sync_all()?;
+            Ok(())
+        })?;
 
         std::fs::remove_file("benchmark_iops_4k.dat")?;
 
-        let iops = (total_ops as f64 / elapsed) as u64;
-        Ok(iops)
+        Ok(BenchOutput::from(exec.units_per_sec() as u64).with_checksum(errors).with_execution(exec))
     }
+
+    fn expected_checksum(&self) -> Option<u64> { Some(0) }
 }
\ No newline at end of file
@@ -0,0 +1,510 @@
+use rayon::prelude::*;
+use std::collections::{BTreeMap, HashMap};
+use std::marker::PhantomData;
+use std::sync::{PoisonError, RwLock};
+use std::time::Instant;
+use anyhow::Result;
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use crate::engines::benchmark::{Benchmark, BenchOutput};
+use crate::engines::timing::measure;
+use crate::model::result::{Category, ExecMode};
+
+// range-sharded store, each shard behind its own lock
+const SHARDS: u64 = 64;
+// operations pre-generated per client, replayed in a loop
+const OPS_POOL: usize = 1 << 18;
+const OPS_PER_UNIT: usize = 16 * 1024;
+// one operation in SAMPLE_EVERY is timed individually for the latency percentiles
+const SAMPLE_EVERY: usize = 32;
+
+/// A key-value structure that can be benchmarked. Keys are dense in `0..keys`.
+/// Add an implementation and list it in `suite()` to benchmark a new store.
+pub trait KvStore: Send + Sync + 'static {
+    const NAME: &'static str;
+    fn with_capacity(keys: usize) -> Self;
+    fn get(&self, key: u64) -> Option<u64>;
+    fn put(&mut self, key: u64, value: u64);
+    fn count(&self) -> usize;
+
+    // visits up to `len` entries in key order from `start`, stops at the first missing key
+    fn scan(&self, start: u64, len: usize, visit: &mut dyn FnMut(u64, u64)) {
+        for key in start..start + len as u64 {
+            match self.get(key) {
+                Some(value) => visit(key, value),
+                None => break,
+            }
+        }
+    }
+}
+
+impl KvStore for HashMap<u64, u64> {
+    const NAME: &'static str = "hashmap";
+    fn with_capacity(keys: usize) -> Self { HashMap::with_capacity(keys) }
+    fn get(&self, key: u64) -> Option<u64> { HashMap::get(self, &key).copied() }
+    fn put(&mut self, key: u64, value: u64) { self.insert(key, value); }
+    fn count(&self) -> usize { self.len() }
+}
+
+impl KvStore for BTreeMap<u64, u64> {
+    const NAME: &'static str = "btreemap";
+    fn with_capacity(_keys: usize) -> Self { BTreeMap::new() }
+    fn get(&self, key: u64) -> Option<u64> { BTreeMap::get(self, &key).copied() }
+    fn put(&mut self, key: u64, value: u64) { self.insert(key, value); }
+    fn count(&self) -> usize { self.len() }
+
+    fn scan(&self, start: u64, len: usize, visit: &mut dyn FnMut(u64, u64)) {
+        for (&key, &value) in self.range(start..).take(len) {
+            visit(key, value);
+        }
+    }
+}
+
+/// Table à adressage ouvert, sondage linéaire
+pub struct OpenAddressing {
+    keys: Vec<u64>,
+    values: Vec<u64>,
+    len: usize,
+}
+
+const EMPTY: u64 = u64::MAX;
+
+impl OpenAddressing {
+    fn slot(&self, key: u64) -> usize {
+        // fibonacci hashing, the high bits are the best mixed
+        let h = key.wrapping_mul(0x9e37_79b9_7f4a_7c15);
+        (h ^ (h >> 32)) as usize & (self.keys.len() - 1)
+    }
+
+    fn grow(&mut self) {
+        let cap = self.keys.len() * 2;
+        let old_keys = std::mem::replace(&mut self.keys, vec![EMPTY; cap]);
+        let old_values = std::mem::replace(&mut self.values, vec![0; cap]);
+        self.len = 0;
+        for (key, value) in old_keys.into_iter().zip(old_values) {
+            if key != EMPTY {
+                self.put(key, value);
+            }
+        }
+    }
+}
+
+impl KvStore for OpenAddressing {
+    const NAME: &'static str = "open addressing";
+
+    fn with_capacity(keys: usize) -> Self {
+        let cap = (keys * 2).next_power_of_two().max(16);
+        OpenAddressing { keys: vec![EMPTY; cap], values: vec![0; cap], len: 0 }
+    }
+
+    fn get(&self, key: u64) -> Option<u64> {
+        let mask = self.keys.len() - 1;
+        let mut i = self.slot(key);
+        loop {
+            match self.keys[i] {
+                k if k == key => return Some(self.values[i]),
+                EMPTY => return None,
+                _ => i = (i + 1) & mask,
+            }
+        }
+    }
+
+    fn put(&mut self, key: u64, value: u64) {
+        // load factor kept under 3/4
+        if (self.len + 1) * 4 > self.keys.len() * 3 {
+            self.grow();
+        }
+        let mask = self.keys.len() - 1;
+        let mut i = self.slot(key);
+        loop {
+            match self.keys[i] {
+                k if k == key => break,
+                EMPTY => {
+                    self.keys[i] = key;
+                    self.len += 1;
+                    break;
+                }
+                _ => i = (i + 1) & mask,
+            }
+        }
+        self.values[i] = value;
+    }
+
+    fn count(&self) -> usize { self.len }
+}
+
+#[derive(Clone, Copy)]
+pub enum Distribution {
+    Uniform,
+    /// YCSB-style scrambled Zipfian, `theta` close to 1 means a few very hot keys
+    Zipf { theta: f64 },
+}
+
+/// Relative share of each operation, usually percentages
+#[derive(Clone, Copy)]
+pub struct Mix {
+    pub read: u32,
+    pub write: u32,
+    pub scan: u32,
+}
+
+#[derive(Clone, Copy)]
+pub struct KvConfig {
+    pub keys: u64,
+    pub distribution: Distribution,
+    pub mix: Mix,
+    pub scan_len: usize,
+}
+
+impl Default for KvConfig {
+    fn default() -> Self {
+        KvConfig {
+            keys: 1_000_000,
+            distribution: Distribution::Zipf { theta: 0.99 },
+            mix: Mix { read: 80, write: 15, scan: 5 },
+            scan_len: 100,
+        }
+    }
+}
+
+// Gray et al., "Quickly generating billion-record synthetic databases"
+struct Zipf {
+    n: u64,
+    theta: f64,
+    alpha: f64,
+    zetan: f64,
+    eta: f64,
+}
+
+impl Zipf {
+    fn new(n: u64, theta: f64) -> Self {
+        let zeta2 = 1.0 + 0.5f64.powf(theta);
+        let zetan: f64 = (1..=n).map(|i| 1.0 / (i as f64).powf(theta)).sum();
+        let eta = (1.0 - (2.0 / n as f64).powf(1.0 - theta)) / (1.0 - zeta2 / zetan);
+        Zipf { n, theta, alpha: 1.0 / (1.0 - theta), zetan, eta }
+    }
+
+    // rank 0 is the most popular
+    fn sample(&self, rng: &mut StdRng) -> u64 {
+        let u: f64 = rng.gen();
+        let uz = u * self.zetan;
+        if uz < 1.0 {
+            0
+        } else if uz < 1.0 + 0.5f64.powf(self.theta) {
+            1
+        } else {
+            ((self.n as f64 * (self.eta * u - self.eta + 1.0).powf(self.alpha)) as u64).min(self.n - 1)
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Op {
+    Read(u64),
+    Write(u64, u64),
+    Scan(u64, usize),
+}
+
+// the low 32 bits of a value are its key, the high bits a version
+fn make_value(key: u64, version: u32) -> u64 {
+    ((version as u64) << 32) | (key & 0xffff_ffff)
+}
+
+fn valid_value(key: u64, value: u64) -> bool {
+    value & 0xffff_ffff == key & 0xffff_ffff
+}
+
+fn generate_ops(config: &KvConfig, zipf: Option<&Zipf>, seed: u64) -> Vec<Op> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    (0..OPS_POOL)
+        .map(|_| {
+            let key = match zipf {
+                // scrambled so the hot keys are spread over the shards
+                Some(zipf) => zipf.sample(&mut rng).wrapping_mul(0x9e37_79b9_7f4a_7c15) % config.keys,
+                None => rng.gen_range(0..config.keys),
+            };
+            let pick = rng.gen_range(0..config.mix.read + config.mix.write + config.mix.scan);
+            if pick < config.mix.read {
+                Op::Read(key)
+            } else if pick < config.mix.read + config.mix.write {
+                Op::Write(key, make_value(key, rng.gen()))
+            } else {
+                Op::Scan(key, config.scan_len)
+            }
+        })
+        .collect()
+}
+
+struct Sharded<S> {
+    shards: Vec<RwLock<S>>,
+    keys: u64,
+    per_shard: u64,
+}
+
+impl<S: KvStore> Sharded<S> {
+    fn load(keys: u64) -> Self {
+        let per_shard = keys.div_ceil(SHARDS);
+        let mut shards: Vec<RwLock<S>> = (0..SHARDS).map(|_| RwLock::new(S::with_capacity(per_shard as usize))).collect();
+        for key in 0..keys {
+            let shard = shards[(key / per_shard) as usize].get_mut().unwrap_or_else(PoisonError::into_inner);
+            shard.put(key, make_value(key, 0));
+        }
+        Sharded { shards, keys, per_shard }
+    }
+
+    fn count(&self) -> usize {
+        self.shards.iter().map(|s| s.read().unwrap_or_else(PoisonError::into_inner).count()).sum()
+    }
+
+    // returns the number of wrong or missing values seen
+    fn execute(&self, op: Op) -> u64 {
+        match op {
+            Op::Read(key) => {
+                let shard = self.shards[(key / self.per_shard) as usize].read().unwrap_or_else(PoisonError::into_inner);
+                match shard.get(key) {
+                    Some(value) if valid_value(key, value) => 0,
+                    _ => 1,
+                }
+            }
+            Op::Write(key, value) => {
+                let mut shard = self.shards[(key / self.per_shard) as usize].write().unwrap_or_else(PoisonError::into_inner);
+                shard.put(key, value);
+                0
+            }
+            Op::Scan(start, len) => {
+                let mut errors = 0;
+                let missing = self.scan(start, len, &mut |k, v| {
+                    if !valid_value(k, v) {
+                        errors += 1;
+                    }
+                });
+                errors + missing
+            }
+        }
+    }
+
+    // a scan can run over the end of a shard into the next one, returns 1 if it stopped on a missing key
+    fn scan(&self, start: u64, len: usize, visit: &mut dyn FnMut(u64, u64)) -> u64 {
+        let mut key = start;
+        let mut left = len;
+        while left > 0 && key < self.keys {
+            let index = key / self.per_shard;
+            let shard = self.shards[index as usize].read().unwrap_or_else(PoisonError::into_inner);
+            let mut visited = 0;
+            shard.scan(key, left, &mut |k, v| {
+                visited += 1;
+                visit(k, v);
+            });
+            if visited == 0 {
+                return 1;
+            }
+            left = left.saturating_sub(visited);
+            key = (index + 1) * self.per_shard;
+        }
+        0
+    }
+}
+
+// un client = un thread qui rejoue sa propre liste d'opérations
+struct Client {
+    ops: Vec<Op>,
+    cursor: usize,
+    errors: u64,
+    samples: Vec<u32>,
+}
+
+impl Client {
+    fn step<S: KvStore>(&mut self, store: &Sharded<S>) {
+        for i in 0..OPS_PER_UNIT {
+            let op = self.ops[(self.cursor + i) % self.ops.len()];
+            if i % SAMPLE_EVERY == 0 {
+                let t = Instant::now();
+                self.errors += store.execute(op);
+                self.samples.push(t.elapsed().as_nanos().min(u32::MAX as u128) as u32);
+            } else {
+                self.errors += store.execute(op);
+            }
+        }
+        self.cursor = (self.cursor + OPS_PER_UNIT) % self.ops.len();
+    }
+}
+
+fn percentile(sorted: &[u32], q: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    sorted[((sorted.len() - 1) as f64 * q).round() as usize] as f64
+}
+
+pub struct KvBench<S> {
+    name: String,
+    config: KvConfig,
+    multi: bool,
+    store: PhantomData<S>,
+}
+
+impl<S: KvStore> KvBench<S> {
+    pub fn new(config: KvConfig, multi: bool) -> Self {
+        let dist = match config.distribution {
+            Distribution::Uniform => "uniform",
+            Distribution::Zipf { .. } => "zipf",
+        };
+        let name = format!("Mem KV {} {} {}", S::NAME, dist, if multi { "Multi" } else { "Single" });
+        Self { name, config, multi, store: PhantomData }
+    }
+}
+
+impl<S: KvStore> Benchmark for KvBench<S> {
+    fn name(&self) -> &str { &self.name }
+    fn weight(&self) -> u64 { 1 }
+    fn category(&self) -> Category { Category::Memory }
+    fn unit(&self) -> &str { "ops/s" }
+    fn mode(&self) -> ExecMode { ExecMode::FixedTime { secs: 2.0 } }
+
+    fn run(&self) -> Result<BenchOutput> {
+        let store = Sharded::<S>::load(self.config.keys);
+        let zipf = match self.config.distribution {
+            Distribution::Zipf { theta } => Some(Zipf::new(self.config.keys, theta)),
+            Distribution::Uniform => None,
+        };
+        let threads = if self.multi { rayon::current_num_threads() } else { 1 };
+        let mut clients: Vec<Client> = (0..threads)
+            .map(|t| Client { ops: generate_ops(&self.config, zipf.as_ref(), t as u64), cursor: 0, errors: 0, samples: Vec::new() })
+            .collect();
+
+        // one work unit = OPS_PER_UNIT operations by every client
+        let exec = measure(self.mode(), |n| {
+            for _ in 0..n {
+                if self.multi {
+                    clients.par_iter_mut().for_each(|c| c.step(&store));
+                } else {
+                    clients[0].step(&store);
+                }
+            }
+            Ok(())
+        })?;
+
+        // writes only update existing keys, the store must not have grown or shrunk
+        let mut errors: u64 = clients.iter().map(|c| c.errors).sum();
+        errors += (store.count() as u64 != self.config.keys) as u64;
+
+        let mut samples: Vec<u32> = clients.into_iter().flat_map(|c| c.samples).collect();
+        samples.sort_unstable();
+        let ops = exec.units_per_sec() * (threads * OPS_PER_UNIT) as f64;
+        Ok(BenchOutput::from(ops as u64)
+            .with_metric("p50", percentile(&samples, 0.5), "ns")
+            .with_metric("p99", percentile(&samples, 0.99), "ns")
+            .with_metric("p99.9", percentile(&samples, 0.999), "ns")
+            .with_checksum(errors)
+            .with_execution(exec))
+    }
+
+    fn expected_checksum(&self) -> Option<u64> { Some(0) }
+}
+
+fn store_suite<S: KvStore>(benches: &mut Vec<Box<dyn Benchmark>>) {
+    let zipf = KvConfig::default();
+    let uniform = KvConfig { distribution: Distribution::Uniform, ..zipf };
+    for config in [zipf, uniform] {
+        benches.push(Box::new(KvBench::<S>::new(config, false)));
+        benches.push(Box::new(KvBench::<S>::new(config, true)));
+    }
+}
+
+/// Default read/write/scan mix over every store, Zipfian and uniform keys, single and multi-threaded
+pub fn suite() -> Vec<Box<dyn Benchmark>> {
+    let mut benches: Vec<Box<dyn Benchmark>> = Vec::new();
+    store_suite::<HashMap<u64, u64>>(&mut benches);
+    store_suite::<BTreeMap<u64, u64>>(&mut benches);
+    store_suite::<OpenAddressing>(&mut benches);
+    benches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // random inserts and updates, enough to make the open addressing table grow and collide
+    fn matches_oracle<S: KvStore>() {
+        let mut store = S::with_capacity(8);
+        let mut oracle = BTreeMap::new();
+        let mut rng = StdRng::seed_from_u64(7);
+        for _ in 0..5000 {
+            let key = rng.gen_range(0..2000);
+            let value = rng.gen();
+            store.put(key, value);
+            oracle.insert(key, value);
+        }
+        assert_eq!(store.count(), oracle.len(), "{}", S::NAME);
+        for key in 0..2100 {
+            assert_eq!(store.get(key), oracle.get(&key).copied(), "{} key {}", S::NAME, key);
+        }
+
+        // keys are dense once every key is written, a scan must then follow the oracle
+        for key in 0..2000 {
+            store.put(key, key * 3);
+            oracle.insert(key, key * 3);
+        }
+        let mut scanned = Vec::new();
+        store.scan(1950, 100, &mut |k, v| scanned.push((k, v)));
+        let expected: Vec<(u64, u64)> = oracle.range(1950..).map(|(&k, &v)| (k, v)).take(100).collect();
+        assert_eq!(scanned, expected, "{}", S::NAME);
+    }
+
+    #[test]
+    fn stores_match_a_btreemap() {
+        matches_oracle::<HashMap<u64, u64>>();
+        matches_oracle::<BTreeMap<u64, u64>>();
+        matches_oracle::<OpenAddressing>();
+    }
+
+    #[test]
+    fn open_addressing_probes_past_collisions() {
+        // keys that all hash to the same slot of a 16 slots table
+        let mut table = OpenAddressing::with_capacity(4);
+        let home = table.slot(0);
+        let keys: Vec<u64> = (0..).filter(|&k| table.slot(k) == home).take(10).collect();
+        for &key in &keys {
+            table.put(key, key + 1);
+        }
+        for &key in &keys {
+            assert_eq!(table.get(key), Some(key + 1));
+        }
+        assert_eq!(table.get(keys[9] + 1), None);
+        assert_eq!(table.count(), keys.len());
+    }
+
+    fn scan_crosses_shards<S: KvStore>() {
+        let store = Sharded::<S>::load(10_000);
+        let start = store.per_shard - 50;
+        let mut keys = Vec::new();
+        let missing = store.scan(start, 100, &mut |k, v| {
+            assert!(valid_value(k, v));
+            keys.push(k);
+        });
+        assert_eq!(missing, 0);
+        assert_eq!(keys, (start..start + 100).collect::<Vec<_>>(), "{}", S::NAME);
+        assert_eq!(store.execute(Op::Scan(start, 100)), 0);
+    }
+
+    #[test]
+    fn scan_over_a_shard_boundary() {
+        scan_crosses_shards::<HashMap<u64, u64>>();
+        scan_crosses_shards::<BTreeMap<u64, u64>>();
+        scan_crosses_shards::<OpenAddressing>();
+    }
+
+    #[test]
+    fn zipf_rank_zero_is_the_most_frequent() {
+        let zipf = Zipf::new(1000, 0.99);
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut counts = vec![0u32; 1000];
+        for _ in 0..100_000 {
+            let rank = zipf.sample(&mut rng);
+            assert!(rank < 1000);
+            counts[rank as usize] += 1;
+        }
+        assert_eq!(counts.iter().max(), Some(&counts[0]));
+        assert!(counts[0] > counts[1] && counts[1] > counts[10]);
+    }
+}
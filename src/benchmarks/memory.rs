@@ -1,86 +1,66 @@
-use std::time::Instant;
+use std::hint::black_box;
 use anyhow::Result;
-use crate::engines::benchmark::Benchmark;
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use crate::engines::benchmark::{Benchmark, BenchOutput};
+use crate::engines::timing::measure;
+use crate::model::result::{Category, Direction, ExecMode, Execution};
+use crate::util::numa::{pin_current_thread, usable_cpus};
 use crate::util::sysinfo::get_system_info;
 use std::thread;
 
-// 1. Opérations base de données (simulées avec vector)
-pub struct MemoryDBOps;
-impl Benchmark for MemoryDBOps {
-    fn name(&self) -> &str { "Mem DB Ops" }
-    fn weight(&self) -> u64 { 2 }
+const MIN_WORKING_SET: usize = 4 * 1024;
+const MAX_WORKING_SET: usize = 4 * 1024 * 1024 * 1024;
+// the scored size, the same on every machine: far beyond any last-level cache, so DRAM
+const SCORED_WORKING_SET: usize = 256 * 1024 * 1024;
+// usize slots per 64-byte cache line, the chase touches one slot per line
+const LINE: usize = 8;
+// walking the whole cycle to check it takes too long beyond this size
+const CYCLE_CHECK_LIMIT: usize = 64 * 1024 * 1024;
 
-    fn run(&self) -> Result<u64> {
-        let mut db = Vec::with_capacity(1_000_000);
-        let start = Instant::now();
-        for i in 0..1_000_000 {
-            db.push(i);
-            let _ = db[i / 2];
-        }
-        let elapsed = start.elapsed().as_secs_f64();
-        let ops = (2_000_000.0 / elapsed) as u64; // approx operations per second
-        Ok(ops)
+// Sattolo's algorithm: a random permutation of the cache lines made of a single cycle,
+// so the prefetcher cannot guess the next line
+pub fn fill_random_cycle(data: &mut [usize], rng: &mut StdRng) {
+    let lines = data.len() / LINE;
+    let mut next: Vec<usize> = (0..lines).collect();
+    for i in (1..lines).rev() {
+        next.swap(i, rng.gen_range(0..i));
+    }
+    for (line, &n) in next.iter().enumerate() {
+        data[line * LINE] = n * LINE;
     }
 }
 
-// 2. Lecture en cache (petit buffer répétitif)
-pub struct MemoryCachedRead;
-impl Benchmark for MemoryCachedRead {
-    fn name(&self) -> &str { "Mem Cached Read" }
-    fn weight(&self) -> u64 { 2 }
-
-    fn run(&self) -> Result<u64> {
-        let size = 8 * 1024 * 1024; // 8MB
-        let data = vec![0u8; size];
-        let start = Instant::now();
-        let mut _sum = 0u64;
-        for _ in 0..100 {
-            for &b in &data {
-                _sum += b as u64;
-            }
+/// Average ns per dependent load along the cycle built by `fill_random_cycle`
+pub fn chase(data: &[usize], mode: ExecMode) -> Result<(Execution, f64)> {
+    // one work unit = one dependent load
+    let mut idx = 0;
+    let exec = measure(mode, |hops| {
+        for _ in 0..hops {
+            idx = data[idx];
         }
-        let elapsed = start.elapsed().as_secs_f64();
-        let mb = (size as f64 * 100.0) / (1024.0 * 1024.0);
-        Ok((mb / elapsed) as u64)
-    }
+        Ok(())
+    })?;
+    black_box(idx);
+    let ns_per_access = 1e9 / exec.units_per_sec();
+    Ok((exec, ns_per_access))
 }
 
-// 3. Lecture non cachée (grand buffer)
-pub struct MemoryUncachedRead;
-impl Benchmark for MemoryUncachedRead {
-    fn name(&self) -> &str { "Mem Uncached Read" }
-    fn weight(&self) -> u64 { 2 }
-
-    fn run(&self) -> Result<u64> {
-        let size = 512 * 1024 * 1024;
-        let data = vec![0u8; size];
-        let start = Instant::now();
-        let mut _sum = 0u64;
-        for &b in &data {
-            _sum += b as u64;
-        }
-        let elapsed = start.elapsed().as_secs_f64();
-        let mb = (size as f64) / (1024.0 * 1024.0);
-        Ok((mb / elapsed) as u64)
+fn cycle_length(data: &[usize]) -> usize {
+    let mut idx = data[0];
+    let mut len = 1;
+    while idx != 0 && len <= data.len() {
+        idx = data[idx];
+        len += 1;
     }
+    len
 }
 
-// 4. Écriture mémoire
-pub struct MemoryWrite;
-impl Benchmark for MemoryWrite {
-    fn name(&self) -> &str { "Mem Write" }
-    fn weight(&self) -> u64 { 2 }
-
-    fn run(&self) -> Result<u64> {
-        let size = 512 * 1024 * 1024;
-        let mut data = vec![0u8; size];
-        let start = Instant::now();
-        for i in 0..size {
-            data[i] = (i % 255) as u8;
-        }
-        let elapsed = start.elapsed().as_secs_f64();
-        let mb = (size as f64) / (1024.0 * 1024.0);
-        Ok((mb / elapsed) as u64)
+fn format_size(bytes: usize) -> String {
+    match bytes {
+        b if b >= 1 << 30 => format!("{} GB", b >> 30),
+        b if b >= 1 << 20 => format!("{} MB", b >> 20),
+        b => format!("{} KB", b >> 10),
     }
 }
 
@@ -89,59 +69,121 @@ pub struct MemoryAvailable;
 impl Benchmark for MemoryAvailable {
     fn name(&self) -> &str { "Mem Available" }
     fn weight(&self) -> u64 { 1 }
+    fn category(&self) -> Category { Category::Memory }
+    fn unit(&self) -> &str { "MB" }
 
-    fn run(&self) -> Result<u64> {
+    fn run(&self) -> Result<BenchOutput> {
         let sys = get_system_info();
-        Ok((sys.available_memory() / 1024) as u64) // MB
+        // sysinfo compte en octets
+        Ok((sys.available_memory() / (1024 * 1024)).into())
     }
 }
 
-// 6. Latence mémoire (accès aléatoire chaîné)
+// 6. Latence : pointer chasing aléatoire sur des working sets de 4 KB à plusieurs GB, score à 256 MB
 pub struct MemoryLatency;
 impl Benchmark for MemoryLatency {
     fn name(&self) -> &str { "Mem Latency" }
     fn weight(&self) -> u64 { 2 }
+    fn category(&self) -> Category { Category::Memory }
+    // the score in ps, whole ns would be too coarse near 100 ns; the curve stays in ns
+    fn unit(&self) -> &str { "ps" }
+    fn direction(&self) -> Direction { Direction::LowerIsBetter }
+    // version 1 scored the largest working set, which depended on free memory
+    fn version(&self) -> u32 { 2 }
+    // per working-set size
+    fn mode(&self) -> ExecMode { ExecMode::FixedTime { secs: 0.2 } }
 
-    fn run(&self) -> Result<u64> {
-        let n = 10_000_000;
-        let mut data = vec![0usize; n];
-        // construire une liste chaînée
-        for i in 0..n {
-            data[i] = (i + 1) % n;
+    fn run(&self) -> Result<BenchOutput> {
+        // keep the largest working set well inside free memory, the sizes past the scored one are metrics only
+        let limit = MAX_WORKING_SET.min(get_system_info().available_memory() as usize / 4);
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut output = BenchOutput::from(0);
+        let mut errors: u64 = 0;
+        let mut size = MIN_WORKING_SET;
+        while size <= limit {
+            let mut data = vec![0usize; size / std::mem::size_of::<usize>()];
+            fill_random_cycle(&mut data, &mut rng);
+            if size <= CYCLE_CHECK_LIMIT && cycle_length(&data) != size / 64 {
+                errors += 1;
+            }
+            let (exec, ns_per_access) = chase(&data, self.mode())?;
+            log::debug!("latency @ {}: {:.1} ns", format_size(size), ns_per_access);
+            if size == SCORED_WORKING_SET {
+                output.value = (ns_per_access * 1000.0).round() as u64;
+            }
+            output = output.with_metric(format!("latency @ {}", format_size(size)), ns_per_access, "ns").with_execution(exec);
+            size *= 2;
         }
-        let mut idx = 0;
-        let start = Instant::now();
-        for _ in 0..n {
-            idx = data[idx];
+        // a smaller set would measure a cache rather than DRAM: count it as an error, so not scored
+        if limit < SCORED_WORKING_SET {
+            log::warn!("Mem Latency: not enough free memory for the {} working set, not scored", format_size(SCORED_WORKING_SET));
+            errors += 1;
         }
-        let elapsed = start.elapsed().as_nanos() as f64;
-        let ns_per_access = elapsed / (n as f64);
-        Ok(ns_per_access as u64)
+        Ok(output.with_checksum(errors))
+    }
+
+    fn expected_checksum(&self) -> Option<u64> { Some(0) }
+}
+
+fn filled_buffer(size: usize) -> Vec<u8> {
+    let mut local = vec![0u8; size];
+    for (i, b) in local.iter_mut().enumerate() {
+        *b = (i % 255) as u8;
     }
+    local
 }
 
 // 7. Mémoire fileté
-pub struct MemoryThreaded;
+pub struct MemoryThreaded {
+    /// One thread per usable CPU (the run's cores if it is pinned), each pinned to its CPU so its buffer is allocated on the local node by first touch
+    pub pin: bool,
+}
 impl Benchmark for MemoryThreaded {
-    fn name(&self) -> &str { "Mem Threaded" }
+    fn name(&self) -> &str { if self.pin { "Mem Threaded Pinned" } else { "Mem Threaded" } }
     fn weight(&self) -> u64 { 2 }
+    fn category(&self) -> Category { Category::Memory }
+    fn unit(&self) -> &str { "MB/s" }
+    fn mode(&self) -> ExecMode { ExecMode::FixedWork { units: 1 } }
 
-    fn run(&self) -> Result<u64> {
-        let threads = num_cpus::get();
+    fn run(&self) -> Result<BenchOutput> {
+        let cpus = usable_cpus();
+        let threads = if self.pin { cpus.len() } else { rayon::current_num_threads() };
         let size = 100 * 1024 * 1024;
-        let start = Instant::now();
-        let mut handles = Vec::new();
-        for _ in 0..threads {
-            handles.push(thread::spawn(move || {
-                let mut local = vec![0u8; size];
-                for i in 0..local.len() {
-                    local[i] = (i % 255) as u8;
-                }
-            }));
-        }
-        for h in handles { h.join().ok(); }
-        let elapsed = start.elapsed().as_secs_f64();
+        // one work unit = every thread allocating and filling its buffer
+        let mut buffers: Vec<Option<Vec<u8>>> = Vec::new();
+        let exec = measure(self.mode(), |n| {
+            for _ in 0..n {
+                buffers = if self.pin {
+                    let handles: Vec<_> = cpus
+                        .iter()
+                        .map(|&cpu| {
+                            thread::spawn(move || {
+                                if let Err(e) = pin_current_thread(cpu) {
+                                    log::warn!("{}", e);
+                                }
+                                filled_buffer(size)
+                            })
+                        })
+                        .collect();
+                    handles.into_iter().map(|h| h.join().ok()).collect()
+                } else {
+                    // un buffer par thread du pool rayon courant (celui du runner s'il est épinglé)
+                    rayon::broadcast(|_| Some(filled_buffer(size)))
+                };
+            }
+            Ok(())
+        })?;
+        // a thread that panicked counts as one error
+        let errors: u64 = buffers
+            .iter()
+            .map(|buf| match buf {
+                Some(local) => local.iter().enumerate().filter(|&(i, &b)| b != (i % 255) as u8).count() as u64,
+                None => 1,
+            })
+            .sum();
         let mb = (size as f64 * threads as f64) / (1024.0 * 1024.0);
-        Ok((mb / elapsed) as u64)
+        Ok(BenchOutput::from((exec.units_per_sec() * mb) as u64).with_checksum(errors).with_execution(exec))
     }
+
+    fn expected_checksum(&self) -> Option<u64> { Some(0) }
 }
\ No newline at end of file
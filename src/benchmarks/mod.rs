@@ -1,10 +1,109 @@
 pub mod cpu;
+pub mod compression;
+pub mod sorting;
+pub mod primes;
+pub mod crypto;
+pub mod uct;
+pub mod physics;
+pub mod kvstore;
+pub mod stream;
+pub mod numa;
 pub mod memory;
 pub mod disk;
 
-use anyhow::Result;
+use crate::engines::benchmark::Benchmark;
+use crate::model::result::StressConfig;
+use cpu::{CpuMultiCore, CpuIntMath, CpuFloatMath, CpuSSE};
+use primes::{CpuPrimeSieve, CpuPrimeSieveMulti};
+use physics::{CpuPhysics, CpuPhysicsMulti};
+use uct::{CpuUCT, CpuUCTMulti};
+use crypto::{CpuSha256, CpuSha512, CpuAesGcm, CpuChaCha20Poly1305, CpuEd25519Sign};
+use memory::{MemoryAvailable, MemoryLatency, MemoryThreaded};
+use stream::{MemoryStream, MemoryStreamMulti};
+use disk::{DiskSequentialRead, DiskSequentialWrite, DiskRandomIOPS32K, DiskRandomIOPS4K};
 
-pub trait Benchmark {
-    fn name(&self) -> &'static str;
-    fn run(&self) -> Result<u64>;
-}
\ No newline at end of file
+/// Full list of benchmarks executed by a run, in order
+pub fn default_suite() -> Vec<Box<dyn Benchmark>> {
+    let mut benches: Vec<Box<dyn Benchmark>> = vec![
+        Box::new(CpuMultiCore),
+        Box::new(CpuIntMath),
+        Box::new(CpuFloatMath),
+        Box::new(CpuPrimeSieve),
+        Box::new(CpuPrimeSieveMulti),
+        Box::new(CpuSSE),
+    ];
+    benches.extend(compression::suite());
+    let rest: Vec<Box<dyn Benchmark>> = vec![
+        Box::new(CpuSha256),
+        Box::new(CpuSha512),
+        Box::new(CpuAesGcm),
+        Box::new(CpuChaCha20Poly1305),
+        Box::new(CpuEd25519Sign),
+        Box::new(CpuPhysics),
+        Box::new(CpuPhysicsMulti),
+    ];
+    benches.extend(rest);
+    benches.extend(sorting::suite());
+    let rest: Vec<Box<dyn Benchmark>> = vec![
+        Box::new(CpuUCT),
+        Box::new(CpuUCTMulti),
+    ];
+    benches.extend(rest);
+    benches.extend(kvstore::suite());
+    let rest: Vec<Box<dyn Benchmark>> = vec![
+        Box::new(MemoryStream),
+        Box::new(MemoryStreamMulti { pin: false }),
+        Box::new(MemoryAvailable),
+        Box::new(MemoryLatency),
+        Box::new(MemoryThreaded { pin: false }),
+    ];
+    benches.extend(rest);
+    benches.extend(numa::suite());
+    let rest: Vec<Box<dyn Benchmark>> = vec![
+        Box::new(DiskSequentialRead),
+        Box::new(DiskSequentialWrite),
+        Box::new(DiskRandomIOPS32K),
+        Box::new(DiskRandomIOPS4K),
+    ];
+    benches.extend(rest);
+    benches
+}
+
+/// Every benchmark this build knows, including the NUMA ones left out of the suite on
+/// single-node machines; used to fill in metadata when re-scoring saved results
+pub fn catalog() -> Vec<Box<dyn Benchmark>> {
+    let mut benches = default_suite();
+    let numa: Vec<Box<dyn Benchmark>> = vec![
+        Box::new(MemoryStreamMulti { pin: true }),
+        Box::new(MemoryThreaded { pin: true }),
+        Box::new(numa::MemoryNumaMatrix),
+    ];
+    for bench in numa {
+        if !benches.iter().any(|b| b.id() == bench.id()) {
+            benches.push(bench);
+        }
+    }
+    benches
+}
+
+/// Workloads looped by the stress mode, each one verified on every pass
+pub fn stress_suite(config: &StressConfig) -> Vec<Box<dyn Benchmark>> {
+    let mut benches: Vec<Box<dyn Benchmark>> = Vec::new();
+    if config.cpu {
+        // vérification : somme connue, empreinte SHA-256, nombre de premiers
+        benches.push(Box::new(CpuMultiCore));
+        benches.push(Box::new(CpuSha256));
+        benches.push(Box::new(CpuPrimeSieveMulti));
+    }
+    if config.memory {
+        // vérification : motifs relus après écriture
+        benches.push(Box::new(MemoryStreamMulti { pin: false }));
+        benches.push(Box::new(MemoryThreaded { pin: false }));
+    }
+    if config.disk {
+        // vérification : contenu relu identique au contenu écrit
+        benches.push(Box::new(DiskSequentialWrite));
+        benches.push(Box::new(DiskSequentialRead));
+    }
+    benches
+}
@@ -0,0 +1,91 @@
+use anyhow::{bail, Result};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use crate::engines::benchmark::{Benchmark, BenchOutput};
+use crate::model::result::{Category, ExecMode};
+use crate::util::numa::{bind_to_node, nodes, pinned_pool, usable_cpus};
+use super::memory::{chase, fill_random_cycle, MemoryThreaded};
+use super::stream::{array_len, stream, MemoryStreamMulti};
+
+// une mesure par paire (noeud CPU, noeud mémoire)
+const PAIR_MODE: ExecMode = ExecMode::FixedTime { secs: 0.25 };
+
+// Matrice bande passante / latence locale vs distante pour chaque paire de noeuds
+pub struct MemoryNumaMatrix;
+impl Benchmark for MemoryNumaMatrix {
+    fn name(&self) -> &str { "Mem NUMA Matrix" }
+    fn weight(&self) -> u64 { 1 }
+    fn category(&self) -> Category { Category::Memory }
+    // mean local Triad bandwidth, the full matrix is in the metrics
+    fn unit(&self) -> &str { "MB/s" }
+    fn mode(&self) -> ExecMode { PAIR_MODE }
+
+    fn run(&self) -> Result<BenchOutput> {
+        let nodes = nodes();
+        if nodes.is_empty() {
+            bail!("no NUMA topology found");
+        }
+        let mut rng = StdRng::seed_from_u64(11);
+        let mut output = BenchOutput::from(0);
+        let mut errors: u64 = 0;
+        let (mut local, mut remote) = (Vec::new(), Vec::new());
+
+        // only the run's cores measure, a node without any of them is left out as a CPU node
+        let usable = usable_cpus();
+        for cpu_node in &nodes {
+            let cpus: Vec<usize> = cpu_node.cpus.iter().copied().filter(|c| usable.contains(c)).collect();
+            if cpus.is_empty() {
+                log::info!("NUMA matrix: no selected core on node {}, skipped as a CPU node", cpu_node.id);
+                continue;
+            }
+            // all the chosen CPUs of the node for bandwidth, a single one for latency
+            let pool = pinned_pool(&cpus)?;
+            let single = pinned_pool(&cpus[..1])?;
+            for mem_node in &nodes {
+                let pair = format!("{}→{}", cpu_node.id, mem_node.id);
+
+                let bw = stream(PAIR_MODE, true, Some(&pool), Some(mem_node.id))?;
+                errors += bw.checksum.unwrap_or(1);
+                let triad = bw.value as f64;
+
+                let ns = single.install(|| -> Result<f64> {
+                    let mut data = vec![0usize; array_len()];
+                    bind_to_node(&data, mem_node.id)?;
+                    fill_random_cycle(&mut data, &mut rng);
+                    Ok(chase(&data, PAIR_MODE)?.1)
+                })?;
+
+                if cpu_node.id == mem_node.id { local.push(triad) } else { remote.push(triad) }
+                output = output
+                    .with_metric(format!("bw {}", pair), triad / 1000.0, "GB/s")
+                    .with_metric(format!("lat {}", pair), ns, "ns");
+            }
+        }
+
+        // nothing measured counts as one error: the benchmark stays unscored, the run goes on
+        if local.is_empty() {
+            log::warn!("NUMA matrix: none of the selected cores is on a NUMA node");
+            return Ok(output.with_checksum(errors + 1));
+        }
+        let mean = |v: &[f64]| v.iter().sum::<f64>() / v.len() as f64;
+        output.value = mean(&local) as u64;
+        if !remote.is_empty() {
+            output = output.with_metric("remote/local", mean(&remote) / mean(&local), "x");
+        }
+        Ok(output.with_checksum(errors))
+    }
+
+    fn expected_checksum(&self) -> Option<u64> { Some(0) }
+}
+
+/// Pinned variants and the node matrix, only on machines with more than one NUMA node
+pub fn suite() -> Vec<Box<dyn Benchmark>> {
+    if nodes().len() < 2 {
+        return Vec::new();
+    }
+    vec![
+        Box::new(MemoryStreamMulti { pin: true }),
+        Box::new(MemoryThreaded { pin: true }),
+        Box::new(MemoryNumaMatrix),
+    ]
+}
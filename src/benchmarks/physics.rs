@@ -0,0 +1,152 @@
+use rayon::prelude::*;
+use std::hint::black_box;
+use anyhow::Result;
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use crate::engines::benchmark::{Benchmark, BenchOutput};
+use crate::engines::timing::measure;
+use crate::model::result::{Category, ExecMode};
+
+const BODIES: usize = 1024;
+const DT: f64 = 1e-4;
+const SOFTENING: f64 = 0.01;
+// leapfrog is symplectic: relative energy drift stays tiny if the physics is right
+const MAX_ENERGY_DRIFT: f64 = 1e-3;
+
+#[derive(Clone, Copy)]
+struct Body {
+    pos: [f64; 3],
+    vel: [f64; 3],
+    mass: f64,
+}
+
+// conditions initiales fixes : amas sphérique avec graine constante
+fn initial_bodies(n: usize, seed: u64) -> Vec<Body> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    (0..n)
+        .map(|_| {
+            let mut pos = [0.0; 3];
+            loop {
+                for p in pos.iter_mut() {
+                    *p = rng.gen_range(-1.0..1.0);
+                }
+                if pos.iter().map(|p| p * p).sum::<f64>() <= 1.0 {
+                    break;
+                }
+            }
+            let vel = [rng.gen_range(-0.05..0.05), rng.gen_range(-0.05..0.05), rng.gen_range(-0.05..0.05)];
+            Body { pos, vel, mass: 1.0 / n as f64 }
+        })
+        .collect()
+}
+
+fn acceleration_on(i: usize, bodies: &[Body]) -> [f64; 3] {
+    let pi = bodies[i].pos;
+    let mut acc = [0.0; 3];
+    for (j, other) in bodies.iter().enumerate() {
+        if i == j {
+            continue;
+        }
+        let d = [other.pos[0] - pi[0], other.pos[1] - pi[1], other.pos[2] - pi[2]];
+        let dist2 = d[0] * d[0] + d[1] * d[1] + d[2] * d[2] + SOFTENING * SOFTENING;
+        let inv = other.mass / (dist2 * dist2.sqrt());
+        acc[0] += d[0] * inv;
+        acc[1] += d[1] * inv;
+        acc[2] += d[2] * inv;
+    }
+    acc
+}
+
+fn accelerations(bodies: &[Body], parallel: bool) -> Vec<[f64; 3]> {
+    if parallel {
+        (0..bodies.len()).into_par_iter().map(|i| acceleration_on(i, bodies)).collect()
+    } else {
+        (0..bodies.len()).map(|i| acceleration_on(i, bodies)).collect()
+    }
+}
+
+// kick-drift-kick leapfrog, `acc` holds the accelerations of the current positions
+fn step(bodies: &mut [Body], acc: &mut Vec<[f64; 3]>, parallel: bool) {
+    for (b, a) in bodies.iter_mut().zip(acc.iter()) {
+        for ((v, p), a) in b.vel.iter_mut().zip(b.pos.iter_mut()).zip(a) {
+            *v += a * DT * 0.5;
+            *p += *v * DT;
+        }
+    }
+    *acc = accelerations(bodies, parallel);
+    for (b, a) in bodies.iter_mut().zip(acc.iter()) {
+        for (v, a) in b.vel.iter_mut().zip(a) {
+            *v += a * DT * 0.5;
+        }
+    }
+}
+
+fn total_energy(bodies: &[Body]) -> f64 {
+    let mut energy = 0.0;
+    for (i, b) in bodies.iter().enumerate() {
+        energy += 0.5 * b.mass * b.vel.iter().map(|v| v * v).sum::<f64>();
+        for other in &bodies[i + 1..] {
+            let d = [other.pos[0] - b.pos[0], other.pos[1] - b.pos[1], other.pos[2] - b.pos[2]];
+            let dist = (d[0] * d[0] + d[1] * d[1] + d[2] * d[2] + SOFTENING * SOFTENING).sqrt();
+            energy -= b.mass * other.mass / dist;
+        }
+    }
+    energy
+}
+
+fn simulate(mode: ExecMode, parallel: bool) -> Result<BenchOutput> {
+    let mut bodies = initial_bodies(BODIES, 2024);
+    let initial_energy = total_energy(&bodies);
+    let mut acc = accelerations(&bodies, parallel);
+
+    // one work unit = one time step; calibration steps are simulated too
+    let mut steps: u64 = 0;
+    let exec = measure(mode, |n| {
+        for _ in 0..n {
+            step(&mut bodies, &mut acc, parallel);
+            black_box(&mut bodies);
+            steps += 1;
+        }
+        Ok(())
+    })?;
+
+    let drift = ((total_energy(&bodies) - initial_energy) / initial_energy).abs();
+    let errors = (drift >= MAX_ENERGY_DRIFT) as u64;
+
+    let interactions = exec.units_per_sec() * (BODIES * (BODIES - 1)) as f64;
+    Ok(BenchOutput::from(interactions as u64)
+        .with_metric("steps", steps as f64, "")
+        .with_metric("energy drift", drift, "")
+        .with_checksum(errors)
+        .with_execution(exec))
+}
+
+// 1. simulation n-corps gravitationnelle mono-thread
+pub struct CpuPhysics;
+impl Benchmark for CpuPhysics {
+    fn name(&self) -> &str { "CPU Physics" }
+    fn weight(&self) -> u64 { 2 }
+    fn category(&self) -> Category { Category::Cpu }
+    fn unit(&self) -> &str { "interactions/s" }
+    fn mode(&self) -> ExecMode { ExecMode::FixedTime { secs: 3.0 } }
+    fn run(&self) -> Result<BenchOutput> {
+        simulate(self.mode(), false)
+    }
+
+    fn expected_checksum(&self) -> Option<u64> { Some(0) }
+}
+
+// 2. simulation n-corps parallèle (rayon)
+pub struct CpuPhysicsMulti;
+impl Benchmark for CpuPhysicsMulti {
+    fn name(&self) -> &str { "CPU Physics Multi" }
+    fn weight(&self) -> u64 { 2 }
+    fn category(&self) -> Category { Category::Cpu }
+    fn unit(&self) -> &str { "interactions/s" }
+    fn mode(&self) -> ExecMode { ExecMode::FixedTime { secs: 3.0 } }
+    fn run(&self) -> Result<BenchOutput> {
+        simulate(self.mode(), true)
+    }
+
+    fn expected_checksum(&self) -> Option<u64> { Some(0) }
+}
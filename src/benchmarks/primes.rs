@@ -0,0 +1,116 @@
+use rayon::prelude::*;
+use std::hint::black_box;
+use anyhow::Result;
+use crate::engines::benchmark::{Benchmark, BenchOutput};
+use crate::engines::timing::measure;
+use crate::model::result::{Category, ExecMode};
+
+// plage fixe : tous les entiers de [0, LIMIT)
+const LIMIT: u64 = 100_000_000;
+// pi(10^8), known answer
+const PRIMES_BELOW_LIMIT: u64 = 5_761_455;
+// 256 KB of flags per segment, fits in L2
+const SEGMENT: u64 = 256 * 1024;
+
+// primes up to sqrt(limit), with a plain sieve
+fn base_primes(limit: u64) -> Vec<u64> {
+    let root = (limit as f64).sqrt() as usize + 1;
+    let mut composite = vec![false; root + 1];
+    let mut primes = Vec::new();
+    for i in 2..=root {
+        if !composite[i] {
+            primes.push(i as u64);
+            for j in (i * i..=root).step_by(i) {
+                composite[j] = true;
+            }
+        }
+    }
+    primes
+}
+
+// number of primes in [low, high)
+fn sieve_segment(low: u64, high: u64, primes: &[u64], flags: &mut Vec<bool>) -> u64 {
+    flags.clear();
+    flags.resize((high - low) as usize, true);
+    for &p in primes {
+        if p * p >= high {
+            break;
+        }
+        let start = (p * p).max(low.div_ceil(p) * p);
+        for m in (start..high).step_by(p as usize) {
+            flags[(m - low) as usize] = false;
+        }
+    }
+    // 0 and 1 are not primes
+    for n in low..high.min(2) {
+        flags[n as usize] = false;
+    }
+    flags.iter().filter(|&&f| f).count() as u64
+}
+
+fn count_primes(limit: u64, parallel: bool) -> u64 {
+    let primes = base_primes(limit);
+    let lows: Vec<u64> = (0..limit).step_by(SEGMENT as usize).collect();
+    if parallel {
+        lows.into_par_iter()
+            .map_init(Vec::new, |flags, low| sieve_segment(low, (low + SEGMENT).min(limit), &primes, flags))
+            .sum()
+    } else {
+        let mut flags = Vec::new();
+        lows.into_iter().map(|low| sieve_segment(low, (low + SEGMENT).min(limit), &primes, &mut flags)).sum()
+    }
+}
+
+fn sieve(mode: ExecMode, parallel: bool) -> Result<BenchOutput> {
+    // one work unit = the whole range sieved once; every pass must find pi(LIMIT)
+    let mut errors: u64 = 0;
+    // count reported as the metric: a wrong one wins over the correct passes
+    let mut found: u64 = 0;
+    let exec = measure(mode, |n| {
+        for _ in 0..n {
+            let count = black_box(count_primes(LIMIT, parallel));
+            if count != PRIMES_BELOW_LIMIT {
+                errors += 1;
+                found = count;
+            } else if errors == 0 {
+                found = count;
+            }
+        }
+        Ok(())
+    })?;
+    let sieved = exec.units_per_sec() * LIMIT as f64;
+    Ok(BenchOutput::from(sieved as u64)
+        .with_metric("primes", found as f64, "")
+        .with_checksum(errors)
+        .with_execution(exec))
+}
+
+// 1. crible segmenté mono-thread
+pub struct CpuPrimeSieve;
+impl Benchmark for CpuPrimeSieve {
+    fn name(&self) -> &str { "CPU Prime Sieve" }
+    fn weight(&self) -> u64 { 2 }
+    fn category(&self) -> Category { Category::Cpu }
+    fn unit(&self) -> &str { "numbers/s" }
+    fn mode(&self) -> ExecMode { ExecMode::FixedTime { secs: 3.0 } }
+    fn run(&self) -> Result<BenchOutput> {
+        sieve(self.mode(), false)
+    }
+
+    fn expected_checksum(&self) -> Option<u64> { Some(0) }
+}
+
+// 2. crible segmenté parallèle, un segment par tâche rayon
+pub struct CpuPrimeSieveMulti;
+impl Benchmark for CpuPrimeSieveMulti {
+    fn name(&self) -> &str { "CPU Prime Sieve Multi" }
+    fn weight(&self) -> u64 { 2 }
+    fn category(&self) -> Category { Category::Cpu }
+    fn unit(&self) -> &str { "numbers/s" }
+    fn mode(&self) -> ExecMode { ExecMode::FixedTime { secs: 3.0 } }
+    fn run(&self) -> Result<BenchOutput> {
+        sieve(self.mode(), true)
+    }
+
+    fn expected_checksum(&self) -> Option<u64> { Some(0) }
+}
@@ -0,0 +1,199 @@
+use rayon::prelude::*;
+use std::cmp::Ordering;
+use std::hint::black_box;
+use anyhow::Result;
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use crate::engines::benchmark::{Benchmark, BenchOutput};
+use crate::engines::timing::measure;
+use crate::model::result::{Category, ExecMode};
+
+const ELEMENTS: usize = 1_000_000;
+// strings are much more expensive to compare, keep the run short
+const STRING_ELEMENTS: usize = 250_000;
+
+#[derive(Clone, Copy)]
+pub enum Data { U64, F64, Str, Record }
+
+#[derive(Clone, Copy)]
+pub enum Order { Random, Sorted, Reverse }
+
+#[derive(Clone, Copy)]
+pub enum Algo { Stable, Unstable, Parallel }
+
+impl Data {
+    fn name(&self) -> &str {
+        match self {
+            Data::U64 => "u64",
+            Data::F64 => "f64",
+            Data::Str => "string",
+            Data::Record => "struct",
+        }
+    }
+}
+
+impl Order {
+    fn name(&self) -> &str {
+        match self {
+            Order::Random => "random",
+            Order::Sorted => "sorted",
+            Order::Reverse => "reverse",
+        }
+    }
+}
+
+impl Algo {
+    fn name(&self) -> &str {
+        match self {
+            Algo::Stable => "sort",
+            Algo::Unstable => "sort_unstable",
+            Algo::Parallel => "par_sort",
+        }
+    }
+}
+
+// enregistrement trié sur `key` seulement : beaucoup de doublons, `id` garde l'ordre d'origine
+#[derive(Clone, PartialEq)]
+struct Record {
+    key: u32,
+    id: u32,
+    payload: [u64; 2],
+}
+
+fn random_u64(rng: &mut StdRng) -> Vec<u64> {
+    (0..ELEMENTS).map(|_| rng.gen()).collect()
+}
+
+fn random_f64(rng: &mut StdRng) -> Vec<f64> {
+    (0..ELEMENTS).map(|_| rng.gen_range(-1e6..1e6)).collect()
+}
+
+fn random_strings(rng: &mut StdRng) -> Vec<String> {
+    (0..STRING_ELEMENTS)
+        .map(|_| {
+            let len = rng.gen_range(8..24);
+            (0..len).map(|_| rng.gen_range(b'a'..=b'z') as char).collect()
+        })
+        .collect()
+}
+
+fn random_records(rng: &mut StdRng) -> Vec<Record> {
+    (0..ELEMENTS)
+        .map(|i| Record { key: rng.gen_range(0..1000), id: i as u32, payload: [rng.gen(), rng.gen()] })
+        .collect()
+}
+
+fn arrange<T, F>(mut input: Vec<T>, order: Order, cmp: &F) -> Vec<T>
+where
+    F: Fn(&T, &T) -> Ordering,
+{
+    match order {
+        Order::Random => {}
+        Order::Sorted => input.sort_by(cmp),
+        Order::Reverse => {
+            input.sort_by(cmp);
+            input.reverse();
+        }
+    }
+    input
+}
+
+// one work unit = copy the input into the working buffer and sort it
+fn sort_input<T, F>(mode: ExecMode, algo: Algo, input: Vec<T>, cmp: F) -> Result<BenchOutput>
+where
+    T: Clone + PartialEq + Send,
+    F: Fn(&T, &T) -> Ordering + Sync,
+{
+    let mut reference = input.clone();
+    reference.sort_by(&cmp);
+
+    let mut work = input.clone();
+    let exec = measure(mode, |n| {
+        for _ in 0..n {
+            work.clone_from_slice(&input);
+            match algo {
+                Algo::Stable => work.sort_by(&cmp),
+                Algo::Unstable => work.sort_unstable_by(&cmp),
+                Algo::Parallel => work.par_sort_by(&cmp),
+            }
+            black_box(&mut work);
+        }
+        Ok(())
+    })?;
+
+    // stable sorts must match the reference exactly, unstable ones only up to equal keys
+    let errors = work
+        .iter()
+        .zip(&reference)
+        .filter(|&(a, b)| match algo {
+            Algo::Unstable => cmp(a, b) != Ordering::Equal,
+            Algo::Stable | Algo::Parallel => a != b,
+        })
+        .count() as u64;
+    let elements = exec.units_per_sec() * input.len() as f64;
+    Ok(BenchOutput::from(elements as u64).with_checksum(errors).with_execution(exec))
+}
+
+pub struct SortBench {
+    name: String,
+    data: Data,
+    order: Order,
+    algo: Algo,
+}
+
+impl SortBench {
+    pub fn new(data: Data, order: Order, algo: Algo) -> Self {
+        let name = format!("CPU Sort {} {} {}", data.name(), order.name(), algo.name());
+        Self { name, data, order, algo }
+    }
+}
+
+impl Benchmark for SortBench {
+    fn name(&self) -> &str { &self.name }
+    fn weight(&self) -> u64 { 1 }
+    fn category(&self) -> Category { Category::Cpu }
+    fn unit(&self) -> &str { "elements/s" }
+    fn mode(&self) -> ExecMode { ExecMode::FixedTime { secs: 2.0 } }
+
+    fn run(&self) -> Result<BenchOutput> {
+        // même graine à chaque run
+        let mut rng = StdRng::seed_from_u64(42);
+        match self.data {
+            Data::U64 => {
+                let cmp = |a: &u64, b: &u64| a.cmp(b);
+                sort_input(self.mode(), self.algo, arrange(random_u64(&mut rng), self.order, &cmp), cmp)
+            }
+            Data::F64 => {
+                let cmp = |a: &f64, b: &f64| a.total_cmp(b);
+                sort_input(self.mode(), self.algo, arrange(random_f64(&mut rng), self.order, &cmp), cmp)
+            }
+            Data::Str => {
+                let cmp = |a: &String, b: &String| a.cmp(b);
+                sort_input(self.mode(), self.algo, arrange(random_strings(&mut rng), self.order, &cmp), cmp)
+            }
+            Data::Record => {
+                let cmp = |a: &Record, b: &Record| a.key.cmp(&b.key);
+                sort_input(self.mode(), self.algo, arrange(random_records(&mut rng), self.order, &cmp), cmp)
+            }
+        }
+    }
+
+    fn expected_checksum(&self) -> Option<u64> { Some(0) }
+}
+
+/// Every data type in random order, plus presorted and reverse integer inputs, with each algorithm
+pub fn suite() -> Vec<Box<dyn Benchmark>> {
+    let algos = [Algo::Stable, Algo::Unstable, Algo::Parallel];
+    let mut benches: Vec<Box<dyn Benchmark>> = Vec::new();
+    for data in [Data::U64, Data::F64, Data::Str, Data::Record] {
+        for algo in algos {
+            benches.push(Box::new(SortBench::new(data, Order::Random, algo)));
+        }
+    }
+    for order in [Order::Sorted, Order::Reverse] {
+        for algo in algos {
+            benches.push(Box::new(SortBench::new(Data::U64, order, algo)));
+        }
+    }
+    benches
+}
@@ -0,0 +1,189 @@
+use rayon::prelude::*;
+use std::hint::black_box;
+use anyhow::Result;
+use crate::engines::benchmark::{Benchmark, BenchOutput};
+use crate::engines::timing::measure;
+use crate::model::result::{Category, ExecMode, Execution};
+use crate::util::numa::{bind_to_node, pinned_pool, usable_cpus};
+use crate::util::sysinfo::{get_system_info, last_level_cache_bytes};
+
+const SCALAR: f64 = 3.0;
+// STREAM rule: every array at least 4x the largest cache
+const CACHE_FACTOR: usize = 4;
+const DEFAULT_LLC: usize = 32 * 1024 * 1024;
+const MIN_ARRAY_BYTES: usize = 64 * 1024 * 1024;
+// elements handed to one rayon task
+const CHUNK: usize = 64 * 1024;
+
+pub fn array_len() -> usize {
+    let bytes = (CACHE_FACTOR * last_level_cache_bytes().unwrap_or(DEFAULT_LLC)).max(MIN_ARRAY_BYTES);
+    // the three arrays must fit comfortably in free memory
+    let available = get_system_info().available_memory() as usize;
+    bytes.min(available / 8) / std::mem::size_of::<f64>()
+}
+
+// les quatre noyaux STREAM
+fn copy(c: &mut [f64], a: &[f64]) {
+    c.copy_from_slice(a);
+}
+
+fn scale(b: &mut [f64], c: &[f64]) {
+    for (b, c) in b.iter_mut().zip(c) {
+        *b = SCALAR * c;
+    }
+}
+
+fn add(c: &mut [f64], a: &[f64], b: &[f64]) {
+    for ((c, a), b) in c.iter_mut().zip(a).zip(b) {
+        *c = a + b;
+    }
+}
+
+fn triad(a: &mut [f64], b: &[f64], c: &[f64]) {
+    for ((a, b), c) in a.iter_mut().zip(b).zip(c) {
+        *a = b + SCALAR * c;
+    }
+}
+
+fn run2(parallel: bool, dst: &mut [f64], x: &[f64], kernel: fn(&mut [f64], &[f64])) {
+    if parallel {
+        dst.par_chunks_mut(CHUNK).zip(x.par_chunks(CHUNK)).for_each(|(d, x)| kernel(d, x));
+    } else {
+        kernel(dst, x);
+    }
+}
+
+fn run3(parallel: bool, dst: &mut [f64], x: &[f64], y: &[f64], kernel: fn(&mut [f64], &[f64], &[f64])) {
+    if parallel {
+        dst.par_chunks_mut(CHUNK)
+            .zip(x.par_chunks(CHUNK))
+            .zip(y.par_chunks(CHUNK))
+            .for_each(|((d, x), y)| kernel(d, x, y));
+    } else {
+        kernel(dst, x, y);
+    }
+}
+
+fn fill(parallel: bool, dst: &mut [f64], value: f64) {
+    if parallel {
+        dst.par_chunks_mut(CHUNK).for_each(|d| d.fill(value));
+    } else {
+        dst.fill(value);
+    }
+}
+
+// GB/s with STREAM accounting: bytes read plus bytes written, no write-allocate traffic
+fn gb_per_sec(exec: &Execution, arrays: usize, len: usize) -> f64 {
+    exec.units_per_sec() * (arrays * len * std::mem::size_of::<f64>()) as f64 / 1e9
+}
+
+/// Runs the four kernels; with a `pool` the parallel kernels run on its (pinned) threads,
+/// with a `mem_node` the arrays are bound to that NUMA node
+pub fn stream(mode: ExecMode, parallel: bool, pool: Option<&rayon::ThreadPool>, mem_node: Option<usize>) -> Result<BenchOutput> {
+    match pool {
+        Some(pool) => pool.install(|| run_stream(mode, parallel, mem_node)),
+        None => run_stream(mode, parallel, mem_node),
+    }
+}
+
+fn run_stream(mode: ExecMode, parallel: bool, mem_node: Option<usize>) -> Result<BenchOutput> {
+    let len = array_len();
+    log::debug!("STREAM arrays of {} MB", (len * std::mem::size_of::<f64>()) >> 20);
+    let mut a = vec![0.0f64; len];
+    let mut b = vec![0.0f64; len];
+    let mut c = vec![0.0f64; len];
+    if let Some(node) = mem_node {
+        for array in [&a, &b, &c] {
+            bind_to_node(array, node)?;
+        }
+    }
+    // first touch from the threads that run the kernels, so the pages land on their node
+    fill(parallel, &mut a, 1.0);
+    fill(parallel, &mut b, 2.0);
+    fill(parallel, &mut c, 0.0);
+
+    // one work unit = one pass of the kernel over the arrays
+    let copy_exec = measure(mode, |n| {
+        for _ in 0..n {
+            run2(parallel, &mut c, &a, copy);
+            black_box(&mut c);
+        }
+        Ok(())
+    })?;
+    let scale_exec = measure(mode, |n| {
+        for _ in 0..n {
+            run2(parallel, &mut b, &c, scale);
+            black_box(&mut b);
+        }
+        Ok(())
+    })?;
+    let add_exec = measure(mode, |n| {
+        for _ in 0..n {
+            run3(parallel, &mut c, &a, &b, add);
+            black_box(&mut c);
+        }
+        Ok(())
+    })?;
+    let triad_exec = measure(mode, |n| {
+        for _ in 0..n {
+            run3(parallel, &mut a, &b, &c, triad);
+            black_box(&mut a);
+        }
+        Ok(())
+    })?;
+
+    // no kernel reads its own output, so the values do not depend on the number of passes:
+    // c = 1, b = 3, c = 1 + 3 = 4, a = 3 + 3 * 4 = 15
+    let errors = a.iter().filter(|&&x| x != 15.0).count()
+        + b.iter().filter(|&&x| x != 3.0).count()
+        + c.iter().filter(|&&x| x != 4.0).count();
+
+    let triad_gb = gb_per_sec(&triad_exec, 3, len);
+    Ok(BenchOutput::from((triad_gb * 1000.0) as u64)
+        .with_metric("Copy", gb_per_sec(&copy_exec, 2, len), "GB/s")
+        .with_metric("Scale", gb_per_sec(&scale_exec, 2, len), "GB/s")
+        .with_metric("Add", gb_per_sec(&add_exec, 3, len), "GB/s")
+        .with_metric("Triad", triad_gb, "GB/s")
+        .with_metric("array", (len * std::mem::size_of::<f64>()) as f64 / (1024.0 * 1024.0), "MB")
+        .with_checksum(errors as u64)
+        .with_execution(triad_exec))
+}
+
+// 1. STREAM mono-thread
+pub struct MemoryStream;
+impl Benchmark for MemoryStream {
+    fn name(&self) -> &str { "Mem STREAM Single" }
+    fn weight(&self) -> u64 { 2 }
+    fn category(&self) -> Category { Category::Memory }
+    // Triad bandwidth, the other kernels are metrics
+    fn unit(&self) -> &str { "MB/s" }
+    // per kernel
+    fn mode(&self) -> ExecMode { ExecMode::FixedTime { secs: 1.0 } }
+    fn run(&self) -> Result<BenchOutput> {
+        stream(self.mode(), false, None, None)
+    }
+
+    fn expected_checksum(&self) -> Option<u64> { Some(0) }
+}
+
+// 2. STREAM sur tous les coeurs (rayon), threads éventuellement épinglés un par CPU
+pub struct MemoryStreamMulti {
+    pub pin: bool,
+}
+impl Benchmark for MemoryStreamMulti {
+    fn name(&self) -> &str { if self.pin { "Mem STREAM Multi Pinned" } else { "Mem STREAM Multi" } }
+    fn weight(&self) -> u64 { 2 }
+    fn category(&self) -> Category { Category::Memory }
+    fn unit(&self) -> &str { "MB/s" }
+    fn mode(&self) -> ExecMode { ExecMode::FixedTime { secs: 1.0 } }
+    fn run(&self) -> Result<BenchOutput> {
+        if self.pin {
+            let pool = pinned_pool(&usable_cpus())?;
+            stream(self.mode(), true, Some(&pool), None)
+        } else {
+            stream(self.mode(), true, None, None)
+        }
+    }
+
+    fn expected_checksum(&self) -> Option<u64> { Some(0) }
+}
@@ -0,0 +1,262 @@
+use rayon::prelude::*;
+use std::hint::black_box;
+use anyhow::Result;
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use crate::engines::benchmark::{Benchmark, BenchOutput};
+use crate::engines::timing::measure;
+use crate::model::result::{Category, ExecMode};
+
+const WIDTH: usize = 7;
+const HEIGHT: usize = 6;
+const PLAYOUTS_PER_SEARCH: u32 = 2_000;
+const EXPLORATION: f64 = 1.41;
+// move chosen by the seed 0 search from the opening: a known answer for the whole engine
+const SEED0_BEST_MOVE: usize = 4;
+
+// Puissance 4 : 0 = case vide, 1 et 2 = joueurs
+#[derive(Clone)]
+struct Board {
+    cells: [u8; WIDTH * HEIGHT],
+    heights: [usize; WIDTH],
+    to_move: u8,
+    moves: usize,
+    winner: Option<u8>,
+}
+
+impl Board {
+    fn new() -> Self {
+        Board { cells: [0; WIDTH * HEIGHT], heights: [0; WIDTH], to_move: 1, moves: 0, winner: None }
+    }
+
+    // fixed opening so every run searches the same position
+    fn opening() -> Self {
+        let mut board = Board::new();
+        for col in [3, 3, 2, 4] {
+            board.play(col);
+        }
+        board
+    }
+
+    fn is_over(&self) -> bool {
+        self.winner.is_some() || self.moves == WIDTH * HEIGHT
+    }
+
+    fn legal_moves(&self) -> Vec<usize> {
+        if self.is_over() {
+            return Vec::new();
+        }
+        (0..WIDTH).filter(|&col| self.heights[col] < HEIGHT).collect()
+    }
+
+    fn play(&mut self, col: usize) {
+        let row = self.heights[col];
+        self.cells[row * WIDTH + col] = self.to_move;
+        self.heights[col] += 1;
+        self.moves += 1;
+        if self.connects_four(col, row) {
+            self.winner = Some(self.to_move);
+        }
+        self.to_move = 3 - self.to_move;
+    }
+
+    fn connects_four(&self, col: usize, row: usize) -> bool {
+        let player = self.cells[row * WIDTH + col];
+        for (dc, dr) in [(1i32, 0i32), (0, 1), (1, 1), (1, -1)] {
+            let mut count = 1;
+            for sign in [1, -1] {
+                let mut c = col as i32 + dc * sign;
+                let mut r = row as i32 + dr * sign;
+                while (0..WIDTH as i32).contains(&c)
+                    && (0..HEIGHT as i32).contains(&r)
+                    && self.cells[r as usize * WIDTH + c as usize] == player
+                {
+                    count += 1;
+                    c += dc * sign;
+                    r += dr * sign;
+                }
+            }
+            if count >= 4 {
+                return true;
+            }
+        }
+        false
+    }
+}
+
+struct Node {
+    mv: usize,
+    // player who made `mv`; wins are counted from their point of view
+    player: u8,
+    visits: u32,
+    wins: f64,
+    untried: Vec<usize>,
+    children: Vec<Node>,
+}
+
+impl Node {
+    fn new(mv: usize, player: u8, board: &Board) -> Self {
+        Node { mv, player, visits: 0, wins: 0.0, untried: board.legal_moves(), children: Vec::new() }
+    }
+
+    fn record(&mut self, winner: u8) {
+        self.visits += 1;
+        if winner == self.player {
+            self.wins += 1.0;
+        } else if winner == 0 {
+            self.wins += 0.5;
+        }
+    }
+
+    // UCB1
+    fn select_child(&self) -> usize {
+        let log_visits = (self.visits as f64).ln();
+        let mut best = 0;
+        let mut best_value = f64::MIN;
+        for (i, child) in self.children.iter().enumerate() {
+            let visits = child.visits as f64;
+            let value = child.wins / visits + EXPLORATION * (log_visits / visits).sqrt();
+            if value > best_value {
+                best_value = value;
+                best = i;
+            }
+        }
+        best
+    }
+}
+
+fn rollout(board: &mut Board, rng: &mut StdRng) -> u8 {
+    while !board.is_over() {
+        let moves = board.legal_moves();
+        board.play(moves[rng.gen_range(0..moves.len())]);
+    }
+    board.winner.unwrap_or(0)
+}
+
+// select, expand, simulate, backpropagate; returns the winner (0 = draw)
+fn playout(node: &mut Node, board: &mut Board, rng: &mut StdRng) -> u8 {
+    let winner = if board.is_over() {
+        board.winner.unwrap_or(0)
+    } else if !node.untried.is_empty() {
+        let mv = node.untried.swap_remove(rng.gen_range(0..node.untried.len()));
+        let player = board.to_move;
+        board.play(mv);
+        let mut child = Node::new(mv, player, board);
+        let winner = rollout(board, rng);
+        child.record(winner);
+        node.children.push(child);
+        winner
+    } else {
+        let idx = node.select_child();
+        let child = &mut node.children[idx];
+        board.play(child.mv);
+        playout(child, board, rng)
+    };
+    node.record(winner);
+    winner
+}
+
+fn search(board: &Board, playouts: u32, seed: u64) -> Node {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut root = Node::new(usize::MAX, 3 - board.to_move, board);
+    for _ in 0..playouts {
+        let mut scratch = board.clone();
+        playout(&mut root, &mut scratch, &mut rng);
+    }
+    root
+}
+
+fn best_move(visits: &[u32; WIDTH]) -> usize {
+    (0..WIDTH).max_by_key(|&col| visits[col]).unwrap_or(0)
+}
+
+fn root_visits(root: &Node, visits: &mut [u32; WIDTH]) {
+    for child in &root.children {
+        visits[child.mv] += child.visits;
+    }
+}
+
+// every playout goes through the root and exactly one of its children
+fn tree_errors(root: &Node, playouts: u32) -> u64 {
+    let child_visits: u32 = root.children.iter().map(|c| c.visits).sum();
+    (root.visits != playouts || child_visits != playouts) as u64
+}
+
+// 1. UCT mono-thread
+pub struct CpuUCT;
+impl Benchmark for CpuUCT {
+    fn name(&self) -> &str { "CPU UCT Single" }
+    fn weight(&self) -> u64 { 2 }
+    fn category(&self) -> Category { Category::Cpu }
+    fn unit(&self) -> &str { "playouts/s" }
+    fn mode(&self) -> ExecMode { ExecMode::FixedTime { secs: 3.0 } }
+    fn run(&self) -> Result<BenchOutput> {
+        // one work unit = one full search
+        let board = Board::opening();
+        let mut seed: u64 = 0;
+        let mut errors: u64 = 0;
+        let exec = measure(self.mode(), |n| {
+            for _ in 0..n {
+                let root = search(&board, PLAYOUTS_PER_SEARCH, seed);
+                errors += tree_errors(&root, PLAYOUTS_PER_SEARCH);
+                let mut visits = [0u32; WIDTH];
+                root_visits(&root, &mut visits);
+                let best = black_box(best_move(&visits));
+                if seed == 0 && best != SEED0_BEST_MOVE {
+                    errors += 1;
+                }
+                seed += 1;
+            }
+            Ok(())
+        })?;
+        let playouts = exec.units_per_sec() * PLAYOUTS_PER_SEARCH as f64;
+        Ok(BenchOutput::from(playouts as u64).with_checksum(errors).with_execution(exec))
+    }
+
+    fn expected_checksum(&self) -> Option<u64> { Some(0) }
+}
+
+// 2. UCT multi-thread (root parallelisation: un arbre par thread, visites fusionnées)
+pub struct CpuUCTMulti;
+impl Benchmark for CpuUCTMulti {
+    fn name(&self) -> &str { "CPU UCT Multi" }
+    fn weight(&self) -> u64 { 2 }
+    fn category(&self) -> Category { Category::Cpu }
+    fn unit(&self) -> &str { "playouts/s" }
+    fn mode(&self) -> ExecMode { ExecMode::FixedTime { secs: 3.0 } }
+    fn run(&self) -> Result<BenchOutput> {
+        // one work unit = one round of `threads` independent searches
+        let board = Board::opening();
+        let threads = rayon::current_num_threads() as u64;
+        let mut round: u64 = 0;
+        let mut errors: u64 = 0;
+        let exec = measure(self.mode(), |n| {
+            for _ in 0..n {
+                let roots: Vec<Node> = (0..threads)
+                    .into_par_iter()
+                    .map(|t| search(&board, PLAYOUTS_PER_SEARCH, round * threads + t))
+                    .collect();
+                let mut visits = [0u32; WIDTH];
+                for root in &roots {
+                    errors += tree_errors(root, PLAYOUTS_PER_SEARCH);
+                    root_visits(root, &mut visits);
+                }
+                black_box(best_move(&visits));
+                // the first tree of the first round uses seed 0, same as the single-thread search
+                if round == 0 {
+                    let mut seed0 = [0u32; WIDTH];
+                    root_visits(&roots[0], &mut seed0);
+                    if best_move(&seed0) != SEED0_BEST_MOVE {
+                        errors += 1;
+                    }
+                }
+                round += 1;
+            }
+            Ok(())
+        })?;
+        let playouts = exec.units_per_sec() * (threads * PLAYOUTS_PER_SEARCH as u64) as f64;
+        Ok(BenchOutput::from(playouts as u64).with_checksum(errors).with_execution(exec))
+    }
+
+    fn expected_checksum(&self) -> Option<u64> { Some(0) }
+}
@@ -0,0 +1,106 @@
+use std::path::PathBuf;
+use anyhow::{bail, Context, Result};
+use crate::benchmarks::catalog;
+use crate::engines::profile;
+use crate::engines::score::{changes, rescore, Rescored};
+use crate::export::{render, Format};
+use crate::model::result::{Aggregation, BenchResult};
+
+const USAGE: &str = "usage:
+  obenchmark rescore <result.json> [--profile <id|file>] [--aggregation geometric|arithmetic] [--output <file>] [--format <format>]
+  obenchmark export <result.json> [--format json|csv|markdown|html] [--output <file>]";
+
+/// Runs a command-line subcommand and returns the process exit code
+pub fn run(args: &[String]) -> i32 {
+    let outcome = match args[0].as_str() {
+        "rescore" => rescore_command(&args[1..]),
+        "export" => export_command(&args[1..]),
+        "-h" | "--help" | "help" => {
+            println!("{}", USAGE);
+            return 0;
+        }
+        other => Err(anyhow::anyhow!("unknown command {}\n{}", other, USAGE)),
+    };
+    match outcome {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("error: {:#}", e);
+            1
+        }
+    }
+}
+
+// recalcule les scores d'un résultat sauvegardé et affiche les écarts
+fn rescore_command(args: &[String]) -> Result<()> {
+    let mut input = None;
+    let mut profile_spec = None;
+    let mut aggregation = Aggregation::default();
+    let mut output = None;
+    let mut format = Format::Json;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--format" => format = args.next().context("--format needs a value")?.parse()?,
+            "--profile" => profile_spec = Some(args.next().context("--profile needs a value")?.clone()),
+            "--aggregation" => aggregation = args.next().context("--aggregation needs a value")?.parse()?,
+            "--output" => output = Some(PathBuf::from(args.next().context("--output needs a value")?)),
+            flag if flag.starts_with("--") => bail!("unknown option {}\n{}", flag, USAGE),
+            path if input.is_none() => input = Some(PathBuf::from(path)),
+            extra => bail!("unexpected argument {}\n{}", extra, USAGE),
+        }
+    }
+    let input = input.context(USAGE)?;
+    let profile = match profile_spec {
+        Some(spec) => profile::find(&spec)?,
+        None => profile::bundled(),
+    };
+
+    let result = BenchResult::load(&input)?;
+    let Rescored { result: rescored, skipped } = rescore(&result, &catalog(), &profile, aggregation);
+    let old_profile = if result.profile.is_empty() { "none" } else { &result.profile };
+    println!("{:?} mean, profile {} → {}", aggregation, old_profile, profile.id);
+    for change in changes(&result, &rescored) {
+        println!("{}", change.describe());
+    }
+    if !skipped.is_empty() {
+        println!("not scored, the benchmark changed since the result was saved:");
+        for reason in &skipped {
+            println!("  {}", reason);
+        }
+    }
+    if let Some(interval) = rescored.final_interval {
+        println!("95% interval of the final score: {}–{}", interval.low, interval.high);
+    }
+    if let Some(output) = output {
+        std::fs::write(&output, render(&rescored, format))?;
+        println!("written to {}", output.display());
+    }
+    Ok(())
+}
+
+// un résultat sauvegardé dans un autre format, sur la sortie standard sans --output
+fn export_command(args: &[String]) -> Result<()> {
+    let mut input = None;
+    let mut format = Format::Json;
+    let mut output = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--format" => format = args.next().context("--format needs a value")?.parse()?,
+            "--output" => output = Some(PathBuf::from(args.next().context("--output needs a value")?)),
+            flag if flag.starts_with("--") => bail!("unknown option {}\n{}", flag, USAGE),
+            path if input.is_none() => input = Some(PathBuf::from(path)),
+            extra => bail!("unexpected argument {}\n{}", extra, USAGE),
+        }
+    }
+    let result = BenchResult::load(&input.context(USAGE)?)?;
+    let text = render(&result, format);
+    match output {
+        Some(output) => {
+            std::fs::write(&output, text).with_context(|| format!("cannot write {}", output.display()))?;
+            println!("written to {}", output.display());
+        }
+        None => print!("{}", text),
+    }
+    Ok(())
+}
@@ -1,7 +1,68 @@
 use anyhow::Result;
+use crate::model::result::{Category, Direction, ExecMode, Execution, Metric};
+
+/// Value produced by a benchmark run, plus optional secondary metrics
+/// (ratios, per-variant throughput...) that are shown but not scored.
+pub struct BenchOutput {
+    pub value: u64,
+    pub metrics: Vec<Metric>,
+    /// Known answer or error count computed from the work actually done
+    pub checksum: Option<u64>,
+    pub execution: Option<Execution>,
+}
+
+impl BenchOutput {
+    pub fn with_execution(mut self, execution: Execution) -> Self {
+        self.execution = Some(execution);
+        self
+    }
+
+    pub fn with_checksum(mut self, checksum: u64) -> Self {
+        self.checksum = Some(checksum);
+        self
+    }
+
+    pub fn with_metric(mut self, name: impl Into<String>, value: f64, unit: impl Into<String>) -> Self {
+        self.metrics.push(Metric { name: name.into(), value, unit: unit.into() });
+        self
+    }
+}
+
+impl From<u64> for BenchOutput {
+    fn from(value: u64) -> Self {
+        BenchOutput { value, metrics: Vec::new(), checksum: None, execution: None }
+    }
+}
+
+/// Stable identifier derived from a display name: "CPU AES-256-GCM" -> "cpu_aes_256_gcm"
+pub fn bench_id(name: &str) -> String {
+    let mut id = String::with_capacity(name.len());
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            id.push(c.to_ascii_lowercase());
+        } else if !id.is_empty() && !id.ends_with('_') {
+            id.push('_');
+        }
+    }
+    id.trim_end_matches('_').to_string()
+}
 
 pub trait Benchmark: Send + Sync {
     fn name(&self) -> &str;
+    /// Key of the benchmark in the reference profile and in saved results
+    fn id(&self) -> String { bench_id(self.name()) }
     fn weight(&self) -> u64;
-    fn run(&self) -> Result<u64>;
-}
\ No newline at end of file
+    fn category(&self) -> Category;
+    /// Unit of the value returned by `run` (e.g. "ops/s", "MB/s")
+    fn unit(&self) -> &str;
+    /// Bumped whenever the workload changes, so saved results of the old one are not
+    /// scored against references measured with the new one
+    fn version(&self) -> u32 { 1 }
+    fn direction(&self) -> Direction { Direction::HigherIsBetter }
+    /// Fixed-work or fixed-time execution of the timed section, see `engines::timing::measure`
+    fn mode(&self) -> ExecMode { ExecMode::FixedTime { secs: 5.0 } }
+    fn run(&self) -> Result<BenchOutput>;
+    /// Checksum `run` must report for the result to be trusted; `None` if the
+    /// benchmark cannot be verified (e.g. it only reads a system value)
+    fn expected_checksum(&self) -> Option<u64> { None }
+}
@@ -1,3 +1,8 @@
 pub mod benchmark;
 pub mod runner;
-pub mod score;
\ No newline at end of file
+pub mod score;
+pub mod timing;
+pub mod noise;
+pub mod thermal;
+pub mod stress;
+pub mod profile;
\ No newline at end of file
@@ -0,0 +1,85 @@
+use std::time::{Duration, Instant};
+use sysinfo::{Pid, System};
+use crate::model::result::LoadSample;
+
+// seuils au-delà desquels l'environnement est considéré comme bruité
+const CPU_NOISE_PERCENT: f32 = 10.0;
+const DISK_NOISE_MB_S: f64 = 20.0;
+const LOW_MEMORY_PERCENT: f32 = 10.0;
+// process CPU usage is only meaningful over a long enough window
+const MIN_WINDOW: Duration = Duration::from_millis(500);
+
+/// Samples the load caused by every process except this one
+pub struct NoiseMonitor {
+    sys: System,
+    own: Option<Pid>,
+    last: Instant,
+}
+
+impl NoiseMonitor {
+    pub fn new() -> Self {
+        let mut sys = System::new();
+        sys.refresh_processes();
+        Self { sys, own: sysinfo::get_current_pid().ok(), last: Instant::now() }
+    }
+
+    /// Load since the previous sample (or since `new`), waits if that was too recent
+    pub fn sample(&mut self, label: &str) -> LoadSample {
+        let since = self.last.elapsed();
+        if since < MIN_WINDOW {
+            std::thread::sleep(MIN_WINDOW - since);
+        }
+        self.sys.refresh_processes();
+        self.sys.refresh_memory();
+        let elapsed = self.last.elapsed().as_secs_f64();
+        self.last = Instant::now();
+
+        let mut cpu = 0.0f32;
+        let mut disk_bytes = 0u64;
+        let mut top: Option<(&str, f32)> = None;
+        for (pid, process) in self.sys.processes() {
+            // on Linux threads are listed too, they are already counted in their process
+            if Some(*pid) == self.own || process.thread_kind().is_some() {
+                continue;
+            }
+            let usage = process.cpu_usage();
+            cpu += usage;
+            let disk = process.disk_usage();
+            disk_bytes += disk.read_bytes + disk.written_bytes;
+            if usage > top.map_or(0.0, |(_, u)| u) {
+                top = Some((process.name(), usage));
+            }
+        }
+
+        let total = self.sys.total_memory().max(1) as f32;
+        LoadSample {
+            label: label.to_string(),
+            cpu_percent: cpu / num_cpus::get() as f32,
+            disk_mb_s: disk_bytes as f64 / elapsed / 1e6,
+            available_memory_percent: self.sys.available_memory() as f32 / total * 100.0,
+            top_process: top.map(|(name, _)| name.to_string()),
+        }
+    }
+}
+
+impl Default for NoiseMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Human-readable reasons why a sample is noisy, empty when the machine was quiet
+pub fn warnings(sample: &LoadSample) -> Vec<String> {
+    let mut warnings = Vec::new();
+    if sample.cpu_percent >= CPU_NOISE_PERCENT {
+        let top = sample.top_process.as_deref().unwrap_or("?");
+        warnings.push(format!("{}: other processes use {:.0}% CPU (busiest: {})", sample.label, sample.cpu_percent, top));
+    }
+    if sample.disk_mb_s >= DISK_NOISE_MB_S {
+        warnings.push(format!("{}: other processes use {:.0} MB/s of disk I/O", sample.label, sample.disk_mb_s));
+    }
+    if sample.available_memory_percent < LOW_MEMORY_PERCENT {
+        warnings.push(format!("{}: only {:.0}% of memory available", sample.label, sample.available_memory_percent));
+    }
+    warnings
+}
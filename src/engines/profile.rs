@@ -0,0 +1,82 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+// profil de référence livré avec le binaire
+const BUNDLED: &str = include_str!("../../profiles/default.json");
+
+/// Raw results of a reference machine: each benchmark scores 1000 on it.
+/// The id must change whenever the values do, so results scored against
+/// different references are never mistaken for comparable ones.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Profile {
+    pub id: String,
+    /// Description of the reference machine
+    #[serde(default)]
+    pub machine: String,
+    /// Benchmark id -> reference raw value
+    pub values: BTreeMap<String, f64>,
+}
+
+impl Profile {
+    pub fn value(&self, bench_id: &str) -> Option<f64> {
+        self.values.get(bench_id).copied()
+    }
+}
+
+pub fn bundled() -> Profile {
+    serde_json::from_str(BUNDLED).expect("bundled profile is valid JSON")
+}
+
+/// User profiles live in `<config dir>/obenchmark/profiles/*.json`
+pub fn user_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|d| d.join("obenchmark").join("profiles"))
+}
+
+fn load(path: &PathBuf) -> Result<Profile> {
+    let text = std::fs::read_to_string(path)?;
+    serde_json::from_str(&text).with_context(|| format!("invalid profile {}", path.display()))
+}
+
+/// The bundled profile first, then the valid user profiles sorted by id
+pub fn available() -> Vec<Profile> {
+    let mut profiles = vec![bundled()];
+    let Some(entries) = user_dir().and_then(|d| std::fs::read_dir(d).ok()) else {
+        return profiles;
+    };
+    let mut user: Vec<Profile> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|path| match load(&path) {
+            Ok(profile) => Some(profile),
+            Err(e) => {
+                log::warn!("{:#}", e);
+                None
+            }
+        })
+        .collect();
+    user.sort_by(|a, b| a.id.cmp(&b.id));
+    for profile in user {
+        // un id déjà pris désignerait deux références différentes
+        if profiles.iter().any(|p| p.id == profile.id) {
+            log::warn!("duplicate profile id {}, ignored", profile.id);
+        } else {
+            profiles.push(profile);
+        }
+    }
+    profiles
+}
+
+/// A profile file, or the id of an available profile
+pub fn find(spec: &str) -> Result<Profile> {
+    let path = PathBuf::from(spec);
+    if path.is_file() {
+        return load(&path);
+    }
+    match available().into_iter().find(|p| p.id == spec) {
+        Some(profile) => Ok(profile),
+        None => bail!("unknown profile {}", spec),
+    }
+}
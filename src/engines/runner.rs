@@ -1,50 +1,166 @@
+use std::time::Instant;
 use crossbeam_channel::Sender;
+use rayon::{ThreadPool, ThreadPoolBuilder};
 use crate::engines::benchmark::Benchmark;
-use crate::model::result::{BenchResult, BenchScore};
-use crate::engines::score::compute_final_score;
+use crate::model::result::{Aggregation, BenchResult, BenchScore, LoadSample, RunSettings, Span, StressReport};
+use crate::engines::noise::{warnings, NoiseMonitor};
+use crate::engines::profile::Profile;
+use crate::engines::score::compute_scores;
+use crate::engines::thermal::ThermalSampler;
+use crate::util::numa::{all_cpus, format_cpulist, pinned_pool, restrict_cpus};
+use crate::util::priority::raise_priority;
+use crate::util::sysinfo::{crypto_features, system_summary};
 
 pub enum RunnerEvent {
     BenchStarted(String),
-    BenchFinished(String, u64),
+    BenchFinished(BenchScore),
+    /// Other processes are loading the machine
+    Warning(String),
     Done(BenchResult),
+    StressDone(StressReport),
     Error(String),
 }
 
+// keeps the sample and reports it if the machine was busy
+fn record_load(sample: LoadSample, tx: &Sender<RunnerEvent>, load: &mut Vec<LoadSample>, noisy: &mut bool) {
+    for warning in warnings(&sample) {
+        log::warn!("{}", warning);
+        tx.send(RunnerEvent::Warning(warning)).ok();
+        *noisy = true;
+    }
+    load.push(sample);
+}
+
+/// Applies the priority and core pinning of `settings`; benchmarks then run inside the
+/// returned pool: single-threaded ones stay on one worker, rayon ones use exactly the pool's
+/// threads (one per chosen core, or one per CPU when not pinned)
+pub fn prepare(settings: &mut RunSettings) -> anyhow::Result<ThreadPool> {
+    // the nice value is per thread on Linux: only threads created from here on inherit it, so
+    // every run gets a new pool instead of rayon's global one, whose workers keep the priority
+    // of whichever run started them
+    if settings.high_priority {
+        match raise_priority() {
+            Ok(()) => settings.priority_raised = true,
+            Err(e) => log::warn!("{}", e),
+        }
+    }
+    // the pinned NUMA variants build their own threads, they read the cores from there
+    restrict_cpus(&settings.cores);
+    if settings.cores.is_empty() {
+        return Ok(ThreadPoolBuilder::new().build()?);
+    }
+    // the result records `cores` as pinned: refuse what cannot be
+    let cpus = all_cpus();
+    if let Some(core) = settings.cores.iter().find(|c| !cpus.contains(c)) {
+        anyhow::bail!("cannot pin to core {}: this machine has cores {}", core, format_cpulist(&cpus));
+    }
+    pinned_pool(&settings.cores)
+}
+
+/// Runs one benchmark and checks its checksum; `origin` is the start of the run
+pub fn run_bench(bench: &dyn Benchmark, pool: &ThreadPool, origin: Instant) -> anyhow::Result<BenchScore> {
+    let name = bench.name().to_string();
+    let start_secs = origin.elapsed().as_secs_f64();
+    let output = pool.install(|| bench.run())?;
+    let span = Span { start_secs, end_secs: origin.elapsed().as_secs_f64() };
+
+    let valid = match bench.expected_checksum() {
+        Some(expected) => output.checksum == Some(expected),
+        None => true,
+    };
+    if !valid {
+        log::warn!(
+            "{} failed verification: expected {:#x}, got {:?}",
+            name,
+            bench.expected_checksum().unwrap_or_default(),
+            output.checksum
+        );
+    }
+    log::info!("{}: {} {} in {:.1}s", name, output.value, bench.unit(), span.end_secs - span.start_secs);
+    if let Some(exec) = &output.execution {
+        log::debug!("{}: {:?}, batch {}, {} units in {:.3}s", name, exec.mode, exec.batch, exec.units, exec.elapsed_secs);
+    }
+    Ok(BenchScore {
+        id: bench.id(),
+        name,
+        raw_score: output.value,
+        unit: bench.unit().to_string(),
+        version: bench.version(),
+        weight: bench.weight(),
+        category: Some(bench.category()),
+        direction: bench.direction(),
+        // set by the caller from the active profile
+        reference: None,
+        metrics: output.metrics,
+        valid,
+        execution: output.execution,
+        span: Some(span),
+    })
+}
+
 pub fn run_benchmarks(
     benches: Vec<Box<dyn Benchmark>>,
+    mut settings: RunSettings,
+    profile: Profile,
+    aggregation: Aggregation,
     tx: Sender<RunnerEvent>,
 ) {
     std::thread::spawn(move || {
+        let pool = match prepare(&mut settings) {
+            Ok(pool) => pool,
+            Err(e) => {
+                tx.send(RunnerEvent::Error(e.to_string())).ok();
+                return;
+            }
+        };
+
+        let mut monitor = NoiseMonitor::new();
+        let mut load = Vec::new();
+        let mut noisy = false;
+        record_load(monitor.sample("before run"), &tx, &mut load, &mut noisy);
+
+        // fréquences et températures échantillonnées pendant tout le run
+        let origin = Instant::now();
+        let sampler = ThermalSampler::start(origin);
         let mut scores: Vec<BenchScore> = Vec::new();
+        log::info!("run started: {} benchmarks, profile {}, cores {:?}", benches.len(), profile.id, settings.cores);
 
         for bench in benches {
-            let name = bench.name().to_string();
-            let weight = bench.weight();
-
-            tx.send(RunnerEvent::BenchStarted(name.clone())).ok();
-
-            match bench.run() {
-                Ok(score) => {
-                    scores.push(BenchScore {
-                        name: name.clone(),
-                        raw_score: score,
-                        weight,
-                    });
-                    tx.send(RunnerEvent::BenchFinished(name.clone(), score)).ok();
+            tx.send(RunnerEvent::BenchStarted(bench.name().to_string())).ok();
+
+            match run_bench(bench.as_ref(), &pool, origin) {
+                Ok(mut bench_score) => {
+                    bench_score.reference = profile.value(&bench_score.id);
+                    tx.send(RunnerEvent::BenchFinished(bench_score.clone())).ok();
+                    record_load(monitor.sample(&bench_score.name), &tx, &mut load, &mut noisy);
+                    scores.push(bench_score);
                 }
                 Err(e) => {
+                    log::error!("{}: {:#}", bench.name(), e);
                     tx.send(RunnerEvent::Error(e.to_string())).ok();
                     return;
                 }
             }
         }
 
-        let final_score = compute_final_score(&scores);
+        let thermal = sampler.stop();
+        let summary = compute_scores(&scores, aggregation);
 
         tx.send(RunnerEvent::Done(BenchResult {
             scores,
-            final_score,
+            final_score: summary.final_score,
+            final_interval: summary.final_interval,
+            sub_scores: summary.sub_scores,
+            aggregation,
+            cpu_features: crypto_features(),
+            settings,
+            noisy,
+            load,
+            thermal,
+            profile: profile.id,
+            system: Some(system_summary()),
+            date: chrono::Local::now().to_rfc3339(),
         }))
         .ok();
     });
-}
\ No newline at end of file
+}
@@ -1,54 +1,378 @@
-use crate::model::result::BenchScore;
-
-/// Baselines pour score normalisé
-/// 1000 = machine de base
-const CPU_BASELINE: u64 = 50_000_000;
-const MEM_BASELINE: u64 = 5000;
-const DISK_BASELINE: u64 = 1000;
-
-pub fn normalize(name: &str, raw_score: u64) -> u64 {
-    // Map many benchmark names to coarse categories so baselines stay meaningful
-    let baseline = if name.contains("CPU") {
-        CPU_BASELINE
-    } else if name.to_lowercase().contains("mem") || name.to_lowercase().contains("memory") {
-        MEM_BASELINE
-    } else if name.to_lowercase().contains("disk") || name.to_lowercase().contains("iops") {
-        DISK_BASELINE
-    } else {
-        1000
+use crate::engines::benchmark::{bench_id, Benchmark};
+use crate::engines::profile::Profile;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use crate::model::result::{Aggregation, BenchResult, BenchScore, Category, Direction, Interval, SubScore};
+
+// cap per-benchmark to avoid huge outliers that saturate final average
+const PER_BENCH_MAX: u64 = 10_000;
+const BOOTSTRAP_ROUNDS: usize = 1000;
+const BOOTSTRAP_SEED: u64 = 0x5eed;
+
+/// Score of a raw value against the reference machine's value: 1000 = reference machine
+pub fn normalize(raw_score: u64, direction: Direction, reference: f64) -> u64 {
+    normalize_raw(raw_score as f64, direction, reference) as u64
+}
+
+// same on a fractional raw value, as produced by the bootstrap
+fn normalize_raw(raw: f64, direction: Direction, reference: f64) -> f64 {
+    let ratio = match direction {
+        Direction::HigherIsBetter => raw / reference,
+        // twice as fast = half the latency = twice the score
+        Direction::LowerIsBetter if raw <= 0.0 => return PER_BENCH_MAX as f64,
+        Direction::LowerIsBetter => reference / raw,
     };
+    (ratio * 1000.0).floor().min(PER_BENCH_MAX as f64)
+}
+
+/// Normalized value of a benchmark, `None` when it is not scored (invalid or no reference)
+pub fn normalized(s: &BenchScore) -> Option<u64> {
+    let reference = s.reference.filter(|&r| r > 0.0)?;
+    s.valid.then(|| normalize(s.raw_score, s.direction, reference))
+}
+
+// (value, weight) pairs; a weighted mean with the chosen aggregation, `None` if nothing has weight
+fn mean(values: &[(f64, f64)], aggregation: Aggregation) -> Option<f64> {
+    let total_weight: f64 = values.iter().map(|&(_, w)| w).sum();
+    if total_weight <= 0.0 {
+        return None;
+    }
+    Some(match aggregation {
+        // a zero would swallow the whole product, floor it at 1
+        Aggregation::Geometric => (values.iter().map(|&(v, w)| w * v.max(1.0).ln()).sum::<f64>() / total_weight).exp(),
+        Aggregation::Arithmetic => values.iter().map(|&(v, w)| w * v).sum::<f64>() / total_weight,
+    })
+}
+
+// (category, rounded sub-score, benchmark count) and the final score, with `raws[i]` standing
+// for the raw value of `scores[i]`
+fn aggregate(scores: &[BenchScore], raws: &[f64], aggregation: Aggregation) -> (Vec<(Category, f64, usize)>, f64) {
+    let mut subs = Vec::new();
+    for category in Category::ALL {
+        // benchmarks that failed verification would only add noise (or a huge bogus value)
+        let values: Vec<(f64, f64)> = scores
+            .iter()
+            .zip(raws)
+            .filter(|(s, _)| s.valid && s.category == Some(category))
+            .filter_map(|(s, &raw)| {
+                let reference = s.reference.filter(|&r| r > 0.0)?;
+                Some((normalize_raw(raw, s.direction, reference), s.weight as f64))
+            })
+            .collect();
+        if let Some(score) = mean(&values, aggregation) {
+            subs.push((category, score.round(), values.len()));
+        }
+    }
+    let values: Vec<(f64, f64)> = subs.iter().map(|&(_, score, _)| (score, 1.0)).collect();
+    let final_score = mean(&values, aggregation).map_or(0.0, |score| score.round().min(99_999.0));
+    (subs, final_score)
+}
+
+// relative throughput of one resampled run: the batch durations drawn with replacement
+fn resampled_speed(batch_secs: &[f64], rng: &mut StdRng) -> f64 {
+    let total: f64 = batch_secs.iter().sum();
+    let drawn: f64 = (0..batch_secs.len()).map(|_| batch_secs[rng.gen_range(0..batch_secs.len())]).sum();
+    total / drawn
+}
 
-    // Normalize to a 0-1000 scale relative to the baseline
-    let mut norm = ((raw_score as f64 / baseline as f64) * 1000.0) as u64;
-    // cap per-benchmark to avoid huge outliers that saturate final average
-    const PER_BENCH_MAX: u64 = 10_000;
-    if norm > PER_BENCH_MAX { norm = PER_BENCH_MAX; }
-    norm
-}
-
-pub fn compute_final_score(scores: &[BenchScore]) -> u64 {
-    // Use wider arithmetic (u128) for intermediate sums to avoid overflow
-    let mut total_weight: u128 = 0;
-    let mut total_score: u128 = 0;
-
-    for s in scores {
-        let normalized = normalize(&s.name, s.raw_score) as u128;
-        let weight = s.weight as u128;
-        // debug: print each normalized score and weight
-        eprintln!("[score] {} -> normalized={} weight={}", s.name, normalized, s.weight);
-        total_score = total_score.saturating_add(normalized.saturating_mul(weight));
-        total_weight = total_weight.saturating_add(weight);
-    }
-
-    let score_u64 = if total_weight == 0 {
-        0u64
-    } else {
-        // divide in u128 then clamp to u64
-        let averaged = (total_score / total_weight) as u128;
-        eprintln!("[score] total_score={} total_weight={} averaged={}", total_score, total_weight, averaged);
-        let capped = if averaged > 99_999u128 { 99_999u128 } else { averaged };
-        capped as u64
+fn percentile(sorted: &[f64], p: f64) -> u64 {
+    sorted[((sorted.len() - 1) as f64 * p).round() as usize] as u64
+}
+
+/// 95 % bootstrap intervals of the sub-scores and of the final score. The batch timings of
+/// every fixed-time benchmark are resampled and its raw value scaled by the resampled speed;
+/// benchmarks with a single measurement count as exact. A category without any batches gets
+/// no interval, `None` if no benchmark has batches at all.
+fn bootstrap(scores: &[BenchScore], aggregation: Aggregation) -> Option<(Vec<(Category, Interval)>, Interval)> {
+    // an unscored benchmark adds no uncertainty
+    let samples: Vec<&[f64]> = scores
+        .iter()
+        .map(|s| match (&s.execution, normalized(s)) {
+            (Some(e), Some(_)) => &e.batch_secs[..],
+            _ => &[][..],
+        })
+        .collect();
+    if samples.iter().all(|b| b.len() < 2) {
+        return None;
+    }
+    // graine fixe : le même résultat donne toujours le même intervalle
+    let mut rng = StdRng::seed_from_u64(BOOTSTRAP_SEED);
+    let mut subs: Vec<(Category, Vec<f64>)> = Vec::new();
+    let mut finals = Vec::with_capacity(BOOTSTRAP_ROUNDS);
+    for _ in 0..BOOTSTRAP_ROUNDS {
+        let raws: Vec<f64> = scores
+            .iter()
+            .zip(&samples)
+            .map(|(s, batches)| {
+                let raw = s.raw_score as f64;
+                if batches.len() < 2 {
+                    return raw;
+                }
+                let speed = resampled_speed(batches, &mut rng);
+                match s.direction {
+                    Direction::HigherIsBetter => raw * speed,
+                    Direction::LowerIsBetter => raw / speed,
+                }
+            })
+            .collect();
+        let (round_subs, final_score) = aggregate(scores, &raws, aggregation);
+        for (category, score, _) in round_subs {
+            match subs.iter_mut().find(|(c, _)| *c == category) {
+                Some((_, values)) => values.push(score),
+                None => subs.push((category, vec![score])),
+            }
+        }
+        finals.push(final_score);
+    }
+
+    let interval = |values: &mut Vec<f64>| {
+        values.sort_by(|a, b| a.total_cmp(b));
+        Interval { low: percentile(values, 0.025), high: percentile(values, 0.975) }
     };
+    let sampled = |category: Category| scores.iter().zip(&samples).any(|(s, b)| s.category == Some(category) && b.len() >= 2);
+    let subs = subs
+        .into_iter()
+        .filter(|&(category, _)| sampled(category))
+        .map(|(category, mut values)| (category, interval(&mut values)))
+        .collect();
+    Some((subs, interval(&mut finals)))
+}
+
+/// Final score with its confidence interval, and the per-category sub-scores
+pub struct Scores {
+    pub final_score: u64,
+    pub final_interval: Option<Interval>,
+    pub sub_scores: Vec<SubScore>,
+}
 
-    score_u64
-}
\ No newline at end of file
+/// Sub-score of each category with scored benchmarks, and the final score: the mean of
+/// the sub-scores, so categories count equally whatever their number of benchmarks
+pub fn compute_scores(scores: &[BenchScore], aggregation: Aggregation) -> Scores {
+    for s in scores.iter().filter(|s| s.valid) {
+        // a benchmark missing from the reference profile cannot be put on the same scale
+        match normalized(s) {
+            Some(normalized) => log::debug!("{} -> normalized={} weight={}", s.name, normalized, s.weight),
+            None => log::warn!("{} has no reference value, not scored", s.name),
+        }
+    }
+    let raws: Vec<f64> = scores.iter().map(|s| s.raw_score as f64).collect();
+    let (subs, final_score) = aggregate(scores, &raws, aggregation);
+    let intervals = bootstrap(scores, aggregation);
+
+    let sub_scores = subs
+        .into_iter()
+        .map(|(category, score, count)| {
+            let interval = intervals.as_ref().and_then(|(subs, _)| subs.iter().find(|(c, _)| *c == category)).map(|&(_, i)| i);
+            log::info!("{} -> {} ({} benchmarks)", category.label(), score, count);
+            SubScore { category, score: score as u64, count, interval }
+        })
+        .collect();
+    log::info!("{:?} final={}", aggregation, final_score);
+    Scores { final_score: final_score as u64, final_interval: intervals.map(|(_, i)| i), sub_scores }
+}
+
+/// A re-scored result and the saved benchmarks that could not be scored
+pub struct Rescored {
+    pub result: BenchResult,
+    /// One line per benchmark left unscored, with the reason
+    pub skipped: Vec<String>,
+}
+
+// why a saved score cannot be compared with the references of today's benchmark, if it cannot
+fn mismatch(s: &BenchScore, bench: &dyn Benchmark) -> Option<String> {
+    if s.version != bench.version() {
+        let saved = if s.version == 0 { "an unversioned".to_string() } else { format!("version {} of the", s.version) };
+        return Some(format!("{}: measured by {} workload, now version {}", s.name, saved, bench.version()));
+    }
+    if s.unit != bench.unit() {
+        return Some(format!("{}: saved in {}, now measured in {}", s.name, s.unit, bench.unit()));
+    }
+    None
+}
+
+/// Recomputes the scores of a saved result against `profile` with `aggregation`.
+/// Metadata older results lack (id, category, direction) is taken from `catalog`; a benchmark
+/// whose saved version or unit differs from `catalog` is left unscored, its raw value would
+/// not mean the same thing as the reference
+pub fn rescore(result: &BenchResult, catalog: &[Box<dyn Benchmark>], profile: &Profile, aggregation: Aggregation) -> Rescored {
+    let mut rescored = result.clone();
+    let mut skipped = Vec::new();
+    for s in &mut rescored.scores {
+        if s.id.is_empty() {
+            s.id = bench_id(&s.name);
+        }
+        s.reference = None;
+        let Some(bench) = catalog.iter().find(|b| b.id() == s.id) else {
+            skipped.push(format!("{}: no longer part of OBenchmark", s.name));
+            continue;
+        };
+        s.category = s.category.or(Some(bench.category()));
+        if let Some(reason) = mismatch(s, bench.as_ref()) {
+            log::warn!("{}, not scored", reason);
+            skipped.push(reason);
+            continue;
+        }
+        s.direction = bench.direction();
+        s.reference = profile.value(&s.id);
+    }
+    let scores = compute_scores(&rescored.scores, aggregation);
+    rescored.final_score = scores.final_score;
+    rescored.final_interval = scores.final_interval;
+    rescored.sub_scores = scores.sub_scores;
+    rescored.aggregation = aggregation;
+    rescored.profile = profile.id.clone();
+    Rescored { result: rescored, skipped }
+}
+
+/// A score before and after re-scoring; `None` = not scored
+pub struct ScoreChange {
+    pub label: String,
+    pub before: Option<u64>,
+    pub after: Option<u64>,
+}
+
+impl ScoreChange {
+    pub fn describe(&self) -> String {
+        let show = |v: Option<u64>| v.map_or("-".to_string(), |v| v.to_string());
+        let mut line = format!("{}: {} → {}", self.label, show(self.before), show(self.after));
+        if let (Some(before), Some(after)) = (self.before, self.after) {
+            if before > 0 {
+                line.push_str(&format!(" ({:+.1}%)", (after as f64 - before as f64) / before as f64 * 100.0));
+            }
+        }
+        line
+    }
+}
+
+/// Final score, sub-scores, then every benchmark of `new` and its counterpart in `old`
+/// (a re-scored result, or a baseline that may not have the same benchmarks)
+pub fn changes(old: &BenchResult, new: &BenchResult) -> Vec<ScoreChange> {
+    let mut changes = vec![ScoreChange { label: "Final".to_string(), before: Some(old.final_score), after: Some(new.final_score) }];
+    let sub = |r: &BenchResult, c: Category| r.sub_scores.iter().find(|s| s.category == c).map(|s| s.score);
+    for category in Category::ALL {
+        let (before, after) = (sub(old, category), sub(new, category));
+        if before.is_some() || after.is_some() {
+            changes.push(ScoreChange { label: category.label().to_string(), before, after });
+        }
+    }
+    // matched by id; results saved before ids existed only have names
+    let key = |s: &BenchScore| if s.id.is_empty() { bench_id(&s.name) } else { s.id.clone() };
+    for after in &new.scores {
+        let before = old.scores.iter().find(|b| key(b) == key(after));
+        changes.push(ScoreChange { label: after.name.clone(), before: before.and_then(normalized), after: normalized(after) });
+    }
+    for before in old.scores.iter().filter(|b| !new.scores.iter().any(|a| key(a) == key(b))) {
+        changes.push(ScoreChange { label: before.name.clone(), before: normalized(before), after: None });
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmarks::crypto::CpuSha256;
+    use crate::model::result::{ExecMode, Execution, RunSettings, ThermalTrace};
+
+    fn score(category: Category, raw: u64, batch_secs: Vec<f64>) -> BenchScore {
+        BenchScore {
+            id: format!("{:?}_{}", category, raw),
+            name: format!("{:?} {}", category, raw),
+            raw_score: raw,
+            unit: "ops/s".to_string(),
+            version: 1,
+            weight: 1,
+            category: Some(category),
+            direction: Direction::HigherIsBetter,
+            reference: Some(1000.0),
+            metrics: Vec::new(),
+            valid: true,
+            execution: Some(Execution {
+                mode: ExecMode::FixedTime { secs: 1.0 },
+                batch: 1,
+                units: batch_secs.len() as u64,
+                elapsed_secs: batch_secs.iter().sum(),
+                batch_secs,
+            }),
+            span: None,
+        }
+    }
+
+    #[test]
+    fn interval_contains_the_point_estimate() {
+        let batches = vec![0.10, 0.12, 0.09, 0.11, 0.15, 0.10, 0.08, 0.13];
+        let scores = vec![score(Category::Cpu, 1200, batches.clone()), score(Category::Memory, 800, batches)];
+        for aggregation in [Aggregation::Geometric, Aggregation::Arithmetic] {
+            let result = compute_scores(&scores, aggregation);
+            let interval = result.final_interval.expect("batches give an interval");
+            assert!(interval.low < interval.high);
+            assert!(interval.low <= result.final_score && result.final_score <= interval.high);
+            for sub in &result.sub_scores {
+                let interval = sub.interval.expect("every category is sampled");
+                assert!(interval.low <= sub.score && sub.score <= interval.high);
+            }
+        }
+    }
+
+    #[test]
+    fn no_interval_without_batches() {
+        let scores = vec![score(Category::Cpu, 1200, Vec::new()), score(Category::Disk, 500, vec![0.5])];
+        let result = compute_scores(&scores, Aggregation::Geometric);
+        assert!(result.final_interval.is_none());
+        assert!(result.sub_scores.iter().all(|s| s.interval.is_none()));
+    }
+
+    #[test]
+    fn geometric_mean_floors_zero() {
+        // 0 counts as 1: sqrt(1 × 100)
+        let geometric = mean(&[(0.0, 1.0), (100.0, 1.0)], Aggregation::Geometric).unwrap();
+        assert!((geometric - 10.0).abs() < 1e-9);
+        assert_eq!(mean(&[(0.0, 1.0), (100.0, 1.0)], Aggregation::Arithmetic), Some(50.0));
+        assert_eq!(mean(&[(5.0, 0.0)], Aggregation::Geometric), None);
+    }
+
+    #[test]
+    fn rescore_skips_changed_benchmarks() {
+        let catalog: Vec<Box<dyn Benchmark>> = vec![Box::new(CpuSha256)];
+        let id = catalog[0].id();
+        let profile = Profile { id: "test".to_string(), machine: String::new(), values: [(id.clone(), 2000.0)].into() };
+        let saved = |version: u32, unit: &str| {
+            let mut s = score(Category::Cpu, 2000, Vec::new());
+            s.id = id.clone();
+            s.name = "CPU SHA-256".to_string();
+            s.version = version;
+            s.unit = unit.to_string();
+            BenchResult {
+                scores: vec![s],
+                final_score: 1000,
+                final_interval: None,
+                sub_scores: Vec::new(),
+                aggregation: Aggregation::Geometric,
+                cpu_features: Vec::new(),
+                settings: RunSettings::default(),
+                noisy: false,
+                load: Vec::new(),
+                thermal: ThermalTrace::default(),
+                profile: "test".to_string(),
+                system: None,
+                date: String::new(),
+            }
+        };
+
+        let same = rescore(&saved(1, "MB/s"), &catalog, &profile, Aggregation::Geometric);
+        assert!(same.skipped.is_empty());
+        assert_eq!(same.result.scores[0].reference, Some(2000.0));
+        assert_eq!(same.result.final_score, 1000);
+
+        // another workload version, another unit, or a result saved before versions existed
+        for (version, unit, reason) in [
+            (2, "MB/s", "CPU SHA-256: measured by version 2 of the workload, now version 1"),
+            (1, "KB/s", "CPU SHA-256: saved in KB/s, now measured in MB/s"),
+            (0, "MB/s", "CPU SHA-256: measured by an unversioned workload, now version 1"),
+        ] {
+            let rescored = rescore(&saved(version, unit), &catalog, &profile, Aggregation::Geometric);
+            assert_eq!(rescored.skipped, vec![reason.to_string()]);
+            assert_eq!(rescored.result.scores[0].reference, None);
+            assert_eq!(rescored.result.final_score, 0);
+        }
+    }
+}
@@ -0,0 +1,125 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use crossbeam_channel::Sender;
+use crate::engines::benchmark::Benchmark;
+use crate::engines::runner::{prepare, run_bench, RunnerEvent};
+use crate::engines::thermal::ThermalSampler;
+use crate::model::result::{Direction, RunSettings, StressConfig, StressIteration, StressReport, StressWorkload};
+
+// une perte de débit au-delà de ce seuil entre le début et la fin est signalée
+const DECAY_WARNING_PERCENT: f64 = 10.0;
+// below this, a single slow pass would read as decay
+const MIN_PASSES_FOR_DECAY: usize = 4;
+
+/// Loops `benches` round-robin for the configured duration (or until `stop` is set),
+/// verifying every pass, then sends a `StressDone` report
+pub fn run_stress(
+    benches: Vec<Box<dyn Benchmark>>,
+    config: StressConfig,
+    mut settings: RunSettings,
+    stop: Arc<AtomicBool>,
+    tx: Sender<RunnerEvent>,
+) {
+    std::thread::spawn(move || {
+        if benches.is_empty() {
+            tx.send(RunnerEvent::Error("no stress workload selected".to_string())).ok();
+            return;
+        }
+        let pool = match prepare(&mut settings) {
+            Ok(pool) => pool,
+            Err(e) => {
+                tx.send(RunnerEvent::Error(e.to_string())).ok();
+                return;
+            }
+        };
+
+        let origin = Instant::now();
+        let sampler = ThermalSampler::start(origin);
+        let duration = Duration::from_secs(config.duration_secs);
+        let mut workloads: Vec<StressWorkload> = benches
+            .iter()
+            .map(|b| StressWorkload {
+                name: b.name().to_string(),
+                unit: b.unit().to_string(),
+                direction: b.direction(),
+                iterations: Vec::new(),
+                failures: Vec::new(),
+            })
+            .collect();
+
+        // a pass already started is finished, so the run can overshoot by one benchmark
+        'run: loop {
+            for (bench, workload) in benches.iter().zip(&mut workloads) {
+                if stop.load(Ordering::Relaxed) || origin.elapsed() >= duration {
+                    break 'run;
+                }
+                tx.send(RunnerEvent::BenchStarted(bench.name().to_string())).ok();
+                match run_bench(bench.as_ref(), &pool, origin) {
+                    Ok(score) => {
+                        let t_secs = origin.elapsed().as_secs_f64();
+                        if !score.valid {
+                            let warning = format!("{}: verification failed at {:.0}s", score.name, t_secs);
+                            log::error!("{}", warning);
+                            tx.send(RunnerEvent::Warning(warning)).ok();
+                        }
+                        workload.iterations.push(StressIteration { t_secs, value: score.raw_score, valid: score.valid });
+                        tx.send(RunnerEvent::BenchFinished(score)).ok();
+                    }
+                    // an error is an instability to report, not a reason to stop the burn-in
+                    Err(e) => {
+                        let failure = format!("{} at {:.0}s: {}", bench.name(), origin.elapsed().as_secs_f64(), e);
+                        log::error!("{}", failure);
+                        tx.send(RunnerEvent::Warning(failure.clone())).ok();
+                        workload.failures.push(failure);
+                    }
+                }
+            }
+        }
+
+        tx.send(RunnerEvent::StressDone(StressReport {
+            config,
+            elapsed_secs: origin.elapsed().as_secs_f64(),
+            stopped: stop.load(Ordering::Relaxed),
+            workloads,
+            settings,
+            thermal: sampler.stop(),
+        }))
+        .ok();
+    });
+}
+
+/// Throughput lost between the first and the last quarter of the passes, in percent
+/// (negative when it improved); `None` with too few valid passes to tell
+pub fn decay_percent(workload: &StressWorkload) -> Option<f64> {
+    let values: Vec<f64> = workload.iterations.iter().filter(|i| i.valid).map(|i| i.value as f64).collect();
+    if values.len() < MIN_PASSES_FOR_DECAY {
+        return None;
+    }
+    let quarter = (values.len() / 4).max(1);
+    let first = values[..quarter].iter().sum::<f64>() / quarter as f64;
+    let last = values[values.len() - quarter..].iter().sum::<f64>() / quarter as f64;
+    if first == 0.0 {
+        return None;
+    }
+    Some(match workload.direction {
+        Direction::HigherIsBetter => (first - last) / first * 100.0,
+        Direction::LowerIsBetter => (last - first) / first * 100.0,
+    })
+}
+
+/// Errors and throughput decay found by a stress run, empty when the machine was stable
+pub fn problems(report: &StressReport) -> Vec<String> {
+    let mut problems = Vec::new();
+    for workload in &report.workloads {
+        let invalid = workload.iterations.iter().filter(|i| !i.valid).count();
+        if invalid > 0 {
+            problems.push(format!("{}: {} of {} passes failed verification", workload.name, invalid, workload.iterations.len()));
+        }
+        problems.extend(workload.failures.iter().cloned());
+        if let Some(decay) = decay_percent(workload).filter(|&d| d >= DECAY_WARNING_PERCENT) {
+            problems.push(format!("{}: throughput dropped {:.0}% over the run", workload.name, decay));
+        }
+    }
+    problems
+}
@@ -0,0 +1,104 @@
+use std::path::PathBuf;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+use crossbeam_channel::{bounded, RecvTimeoutError, Sender};
+use sysinfo::{Components, CpuRefreshKind, RefreshKind, System};
+use crate::model::result::{Span, ThermalSample, ThermalTrace};
+
+// assez fin pour voir une chute de fréquence au milieu d'une boucle de 5 s
+const INTERVAL: Duration = Duration::from_millis(250);
+
+/// Background thread recording CPU frequencies and temperatures until `stop`
+pub struct ThermalSampler {
+    stop: Sender<()>,
+    handle: JoinHandle<ThermalTrace>,
+}
+
+impl ThermalSampler {
+    /// Sample times are relative to `origin`, the start of the run
+    pub fn start(origin: Instant) -> Self {
+        let (stop, stopped) = bounded(1);
+        let handle = std::thread::spawn(move || {
+            let mut sensors = Sensors::new();
+            let mut trace = ThermalTrace { sensors: sensors.names(), samples: Vec::new() };
+            loop {
+                trace.samples.push(sensors.sample(origin));
+                match stopped.recv_timeout(INTERVAL) {
+                    Err(RecvTimeoutError::Timeout) => {}
+                    // stop requested, or the runner gave up and dropped the sampler
+                    _ => break,
+                }
+            }
+            trace
+        });
+        Self { stop, handle }
+    }
+
+    pub fn stop(self) -> ThermalTrace {
+        self.stop.send(()).ok();
+        self.handle.join().unwrap_or_default()
+    }
+}
+
+struct Sensors {
+    sys: System,
+    components: Components,
+    // /sys/class/thermal/thermal_zone*: (type, temp file)
+    zones: Vec<(String, PathBuf)>,
+}
+
+impl Sensors {
+    fn new() -> Self {
+        let sys = System::new_with_specifics(RefreshKind::new().with_cpu(CpuRefreshKind::new().with_frequency()));
+        Self { sys, components: Components::new_with_refreshed_list(), zones: thermal_zones() }
+    }
+
+    fn names(&self) -> Vec<String> {
+        let components = self.components.iter().map(|c| c.label().to_string());
+        components.chain(self.zones.iter().map(|(name, _)| name.clone())).collect()
+    }
+
+    fn sample(&mut self, origin: Instant) -> ThermalSample {
+        self.sys.refresh_cpu_frequency();
+        self.components.refresh();
+        // a sensor that cannot be read is recorded as None to keep the columns aligned
+        let mut temps_c: Vec<Option<f32>> = self.components.iter().map(|c| Some(c.temperature()).filter(|t| t.is_finite())).collect();
+        // millidegrees
+        temps_c.extend(self.zones.iter().map(|(_, path)| {
+            std::fs::read_to_string(path)
+                .ok()
+                .and_then(|t| t.trim().parse::<f32>().ok())
+                .map(|t| t / 1000.0)
+        }));
+        ThermalSample {
+            t_secs: origin.elapsed().as_secs_f64(),
+            freq_mhz: self.sys.cpus().iter().map(|cpu| cpu.frequency()).collect(),
+            temps_c,
+        }
+    }
+}
+
+// sysinfo already reads hwmon, the ACPI/SoC thermal zones come on top
+fn thermal_zones() -> Vec<(String, PathBuf)> {
+    let Ok(entries) = std::fs::read_dir("/sys/class/thermal") else {
+        return Vec::new();
+    };
+    let mut zones: Vec<(String, PathBuf)> = entries
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_name().to_string_lossy().starts_with("thermal_zone"))
+        .filter_map(|e| {
+            let kind = std::fs::read_to_string(e.path().join("type")).ok()?;
+            let name = format!("{} ({})", e.file_name().to_string_lossy(), kind.trim());
+            Some((name, e.path().join("temp")))
+        })
+        .collect();
+    zones.sort();
+    zones
+}
+
+/// Samples taken while a benchmark was running
+pub fn samples_in(trace: &ThermalTrace, span: Span) -> &[ThermalSample] {
+    let start = trace.samples.partition_point(|s| s.t_secs < span.start_secs);
+    let end = trace.samples.partition_point(|s| s.t_secs <= span.end_secs);
+    &trace.samples[start..end.max(start)]
+}
@@ -0,0 +1,52 @@
+use std::time::Instant;
+use anyhow::Result;
+use crate::model::result::{ExecMode, Execution};
+
+// a calibrated batch should last about 1/50 of the run, within these bounds
+const TARGET_BATCHES: f64 = 50.0;
+const MIN_BATCH_SECS: f64 = 0.001;
+const MAX_BATCH_SECS: f64 = 0.2;
+
+/// Runs `work(n)`, which must perform `n` work units, according to `mode`.
+///
+/// In fixed-time mode the batch size is first calibrated (doubling until a
+/// batch lasts long enough) so that `Instant::now()` stays out of tight loops;
+/// calibration batches are not part of the measurement.
+pub fn measure(mode: ExecMode, mut work: impl FnMut(u64) -> Result<()>) -> Result<Execution> {
+    match mode {
+        ExecMode::FixedWork { units } => {
+            let start = Instant::now();
+            work(units)?;
+            Ok(Execution { mode, batch: units, units, elapsed_secs: start.elapsed().as_secs_f64(), batch_secs: Vec::new() })
+        }
+        ExecMode::FixedTime { secs } => {
+            let target = (secs / TARGET_BATCHES).clamp(MIN_BATCH_SECS, MAX_BATCH_SECS);
+            let mut batch: u64 = 1;
+            loop {
+                let t = Instant::now();
+                work(batch)?;
+                if t.elapsed().as_secs_f64() >= target {
+                    break;
+                }
+                batch *= 2;
+            }
+
+            // the clock read that ends a batch starts the next one
+            let mut units: u64 = 0;
+            let mut batch_secs = Vec::new();
+            let start = Instant::now();
+            let mut last = start;
+            loop {
+                work(batch)?;
+                units += batch;
+                let now = Instant::now();
+                batch_secs.push((now - last).as_secs_f64());
+                last = now;
+                if (now - start).as_secs_f64() >= secs {
+                    break;
+                }
+            }
+            Ok(Execution { mode, batch, units, elapsed_secs: (last - start).as_secs_f64(), batch_secs })
+        }
+    }
+}
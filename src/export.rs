@@ -0,0 +1,342 @@
+use std::borrow::Cow;
+use std::fmt::Write;
+use crate::engines::score::normalized;
+use crate::model::result::{BenchResult, BenchScore, Direction, ExecMode, Interval};
+
+/// File formats a result can be exported to
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Format {
+    /// The full result, the only format that can be loaded back
+    #[default]
+    Json,
+    /// One row per benchmark
+    Csv,
+    /// Tables to paste into tickets
+    Markdown,
+    /// Self-contained report with system info and charts
+    Html,
+}
+
+impl Format {
+    pub const ALL: [Format; 4] = [Format::Json, Format::Csv, Format::Markdown, Format::Html];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Format::Json => "JSON",
+            Format::Csv => "CSV",
+            Format::Markdown => "Markdown",
+            Format::Html => "HTML",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Format::Json => "json",
+            Format::Csv => "csv",
+            Format::Markdown => "md",
+            Format::Html => "html",
+        }
+    }
+}
+
+impl std::str::FromStr for Format {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            "markdown" | "md" => Ok(Format::Markdown),
+            "html" => Ok(Format::Html),
+            other => anyhow::bail!("unknown format {} (json, csv, markdown or html)", other),
+        }
+    }
+}
+
+/// `result` written in `format`
+pub fn render(result: &BenchResult, format: Format) -> String {
+    match format {
+        Format::Json => serde_json::to_string_pretty(result).expect("a result always serializes"),
+        Format::Csv => csv(result),
+        Format::Markdown => markdown(result),
+        Format::Html => html(result),
+    }
+}
+
+// "4231 ± 37"
+pub fn with_interval(score: u64, interval: Option<Interval>) -> String {
+    match interval {
+        Some(interval) => format!("{} ± {}", score, interval.half_width()),
+        None => score.to_string(),
+    }
+}
+
+/// Machine and run settings of a result, as (label, value) lines
+pub fn system_lines(result: &BenchResult) -> Vec<(&'static str, String)> {
+    let mut lines = Vec::new();
+    match &result.system {
+        Some(system) => {
+            lines.push(("CPU", system.cpu.clone()));
+            lines.push(("Cores", system.cores.to_string()));
+            lines.push(("RAM", format!("{} MB", system.memory_mb)));
+            lines.push(("OS", system.os.clone()));
+        }
+        // résultats d'avant l'enregistrement du système
+        None => lines.push(("System", "not recorded".to_string())),
+    }
+    let features = if result.cpu_features.is_empty() { "none".to_string() } else { result.cpu_features.join(", ") };
+    lines.push(("Crypto acceleration", features));
+    let settings = &result.settings;
+    let cores = if settings.cores.is_empty() {
+        "not pinned".to_string()
+    } else {
+        settings.cores.iter().map(|c| c.to_string()).collect::<Vec<_>>().join(", ")
+    };
+    lines.push(("Pinned cores", cores));
+    let priority = match (settings.high_priority, settings.priority_raised) {
+        (false, _) => "normal",
+        (true, true) => "raised",
+        (true, false) => "normal (raise denied)",
+    };
+    lines.push(("Priority", priority.to_string()));
+    if !result.date.is_empty() {
+        lines.push(("Date", result.date.clone()));
+    }
+    lines
+}
+
+fn direction(direction: Direction) -> &'static str {
+    match direction {
+        Direction::HigherIsBetter => "higher",
+        Direction::LowerIsBetter => "lower",
+    }
+}
+
+fn metrics(score: &BenchScore) -> String {
+    score.metrics.iter().map(|m| format!("{}={} {}", m.name, m.value, m.unit)).collect::<Vec<_>>().join("; ")
+}
+
+// quoted only when needed (RFC 4180)
+fn csv_field(field: &str) -> Cow<'_, str> {
+    if field.contains([',', '"', '\n']) {
+        Cow::Owned(format!("\"{}\"", field.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(field)
+    }
+}
+
+fn csv(result: &BenchResult) -> String {
+    let mut out = String::from("id,name,category,raw_score,unit,direction,reference,normalized,weight,valid,mode,elapsed_secs,metrics\n");
+    for s in &result.scores {
+        let (mode, elapsed) = match &s.execution {
+            Some(exec) => {
+                let mode = match exec.mode {
+                    ExecMode::FixedTime { .. } => "fixed_time",
+                    ExecMode::FixedWork { .. } => "fixed_work",
+                };
+                (mode.to_string(), exec.elapsed_secs.to_string())
+            }
+            None => (String::new(), String::new()),
+        };
+        let fields = [
+            s.id.clone(),
+            s.name.clone(),
+            s.category.map(|c| c.label().to_string()).unwrap_or_default(),
+            s.raw_score.to_string(),
+            s.unit.clone(),
+            direction(s.direction).to_string(),
+            s.reference.map(|r| r.to_string()).unwrap_or_default(),
+            normalized(s).map(|n| n.to_string()).unwrap_or_default(),
+            s.weight.to_string(),
+            s.valid.to_string(),
+            mode,
+            elapsed,
+            metrics(s),
+        ];
+        let row: Vec<Cow<str>> = fields.iter().map(|f| csv_field(f)).collect();
+        out.push_str(&row.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+// a `|` would split the cell
+fn md_cell(text: &str) -> String {
+    text.replace('|', "\\|")
+}
+
+fn markdown(result: &BenchResult) -> String {
+    let mut out = String::from("# OBenchmark result\n\n");
+    writeln!(out, "**Final score: {}** ({:?} mean, profile {})\n", with_interval(result.final_score, result.final_interval), result.aggregation, result.profile).unwrap();
+    if result.noisy {
+        out.push_str("> ⚠ Noisy environment: other processes loaded the machine during the run\n\n");
+    }
+    if !result.sub_scores.is_empty() {
+        out.push_str("| Category | Score | Tests |\n|---|---:|---:|\n");
+        for sub in &result.sub_scores {
+            writeln!(out, "| {} | {} | {} |", sub.category.label(), with_interval(sub.score, sub.interval), sub.count).unwrap();
+        }
+        out.push('\n');
+    }
+    out.push_str("| Benchmark | Category | Raw | Unit | Score |\n|---|---|---:|---|---:|\n");
+    for s in &result.scores {
+        let score = match normalized(s) {
+            Some(n) => n.to_string(),
+            None if !s.valid => "invalid".to_string(),
+            None => "not scored".to_string(),
+        };
+        let unit = match s.direction {
+            Direction::LowerIsBetter => format!("{} (lower is better)", s.unit),
+            Direction::HigherIsBetter => s.unit.clone(),
+        };
+        let category = s.category.map_or("", |c| c.label());
+        writeln!(out, "| {} | {} | {} | {} | {} |", md_cell(&s.name), category, s.raw_score, md_cell(&unit), score).unwrap();
+    }
+    out.push_str("\n| System | |\n|---|---|\n");
+    for (label, value) in system_lines(result) {
+        writeln!(out, "| {} | {} |", label, md_cell(&value)).unwrap();
+    }
+    out
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+const STYLE: &str = "body{font-family:sans-serif;max-width:960px;margin:2em auto;color:#222}\
+table{border-collapse:collapse;margin:1em 0}td,th{border:1px solid #ccc;padding:4px 8px;text-align:left}\
+td.num{text-align:right}.invalid{color:#c00}.final{font-size:2.5em;font-weight:bold;margin:0}\
+svg text{font-size:12px;font-family:sans-serif}";
+
+// barres horizontales, avec un trait pointillé à 1000 = machine de référence
+fn bar_chart(bars: &[(String, Option<u64>)]) -> String {
+    const LABEL_WIDTH: f64 = 240.0;
+    const BAR_WIDTH: f64 = 480.0;
+    const ROW_HEIGHT: f64 = 22.0;
+    let max = bars.iter().filter_map(|b| b.1).max().unwrap_or(0).max(1000) as f64;
+    let width = LABEL_WIDTH + BAR_WIDTH + 80.0;
+    let height = ROW_HEIGHT * bars.len() as f64;
+    let mut svg = format!(r#"<svg xmlns="http://www.w3.org/2000/svg" width="{0}" height="{1}" viewBox="0 0 {0} {1}">"#, width, height);
+    for (i, (label, value)) in bars.iter().enumerate() {
+        let y = i as f64 * ROW_HEIGHT;
+        write!(svg, r#"<text x="{}" y="{}" text-anchor="end">{}</text>"#, LABEL_WIDTH - 8.0, y + 15.0, escape(label)).unwrap();
+        match value {
+            Some(v) => {
+                let w = *v as f64 / max * BAR_WIDTH;
+                write!(svg, r##"<rect x="{}" y="{}" width="{:.1}" height="{}" fill="#4a7bd0"/>"##, LABEL_WIDTH, y + 3.0, w, ROW_HEIGHT - 6.0).unwrap();
+                write!(svg, r#"<text x="{:.1}" y="{}">{}</text>"#, LABEL_WIDTH + w + 4.0, y + 15.0, v).unwrap();
+            }
+            None => write!(svg, r##"<text x="{}" y="{}" fill="#888">not scored</text>"##, LABEL_WIDTH, y + 15.0).unwrap(),
+        }
+    }
+    let x = LABEL_WIDTH + 1000.0 / max * BAR_WIDTH;
+    write!(svg, r##"<line x1="{0:.1}" y1="0" x2="{0:.1}" y2="{1}" stroke="#c00" stroke-dasharray="4 3"/></svg>"##, x, height).unwrap();
+    svg
+}
+
+// one series over the run, with its range and duration as labels
+fn line_chart(points: &[(f64, f64)], unit: &str, color: &str) -> String {
+    const WIDTH: f64 = 720.0;
+    const HEIGHT: f64 = 160.0;
+    const MARGIN: f64 = 50.0;
+    let t_end = points.last().map_or(1.0, |p| p.0).max(1e-3);
+    let low = points.iter().map(|p| p.1).fold(f64::INFINITY, f64::min);
+    let high = points.iter().map(|p| p.1).fold(f64::NEG_INFINITY, f64::max);
+    let range = (high - low).max(1.0);
+    let coords: Vec<String> = points
+        .iter()
+        .map(|&(t, v)| format!("{:.1},{:.1}", MARGIN + t / t_end * (WIDTH - MARGIN), HEIGHT - 20.0 - (v - low) / range * (HEIGHT - 30.0)))
+        .collect();
+    format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}"><polyline fill="none" stroke="{color}" stroke-width="1.5" points="{points}"/><text x="0" y="14">{high:.0} {unit}</text><text x="0" y="{low_y}">{low:.0} {unit}</text><text x="{w}" y="{h}" text-anchor="end">{t_end:.0} s</text></svg>"##,
+        w = WIDTH,
+        h = HEIGHT,
+        color = color,
+        points = coords.join(" "),
+        high = high,
+        low = low,
+        low_y = HEIGHT - 20.0,
+        unit = unit,
+        t_end = t_end,
+    )
+}
+
+fn html(result: &BenchResult) -> String {
+    let mut out = String::new();
+    write!(
+        out,
+        r#"<!DOCTYPE html><html><head><meta charset="utf-8"><title>OBenchmark {}</title><style>{}</style></head><body><h1>OBenchmark</h1>"#,
+        result.final_score, STYLE
+    )
+    .unwrap();
+    write!(out, r#"<p class="final">{}</p>"#, escape(&with_interval(result.final_score, result.final_interval))).unwrap();
+    write!(out, "<p>{:?} mean · profile {}</p>", result.aggregation, escape(&result.profile)).unwrap();
+    if result.noisy {
+        out.push_str(r#"<p class="invalid">⚠ Noisy environment: other processes loaded the machine during the run</p>"#);
+    }
+
+    if !result.sub_scores.is_empty() {
+        out.push_str("<h2>Sub-scores</h2>");
+        let bars: Vec<(String, Option<u64>)> = result.sub_scores.iter().map(|s| (s.category.label().to_string(), Some(s.score))).collect();
+        out.push_str(&bar_chart(&bars));
+        out.push_str("<table><tr><th>Category</th><th>Score</th><th>Tests</th></tr>");
+        for sub in &result.sub_scores {
+            write!(out, r#"<tr><td>{}</td><td class="num">{}</td><td class="num">{}</td></tr>"#, sub.category.label(), with_interval(sub.score, sub.interval), sub.count).unwrap();
+        }
+        out.push_str("</table>");
+    }
+
+    out.push_str("<h2>Benchmarks</h2>");
+    let bars: Vec<(String, Option<u64>)> = result.scores.iter().map(|s| (s.name.clone(), normalized(s))).collect();
+    out.push_str(&bar_chart(&bars));
+    out.push_str("<table><tr><th>Benchmark</th><th>Category</th><th>Raw</th><th>Unit</th><th>Score</th><th>Metrics</th></tr>");
+    for s in &result.scores {
+        let (class, score) = match normalized(s) {
+            Some(n) => ("", n.to_string()),
+            None if !s.valid => ("invalid", "invalid".to_string()),
+            None => ("", "not scored".to_string()),
+        };
+        write!(
+            out,
+            r#"<tr class="{}"><td>{}</td><td>{}</td><td class="num">{}</td><td>{}</td><td class="num">{}</td><td>{}</td></tr>"#,
+            class,
+            escape(&s.name),
+            s.category.map_or("", |c| c.label()),
+            s.raw_score,
+            escape(&s.unit),
+            score,
+            escape(&metrics(s))
+        )
+        .unwrap();
+    }
+    out.push_str("</table>");
+
+    let samples = &result.thermal.samples;
+    if !samples.is_empty() {
+        out.push_str("<h2>Frequency and temperature</h2>");
+        let freq: Vec<(f64, f64)> = samples
+            .iter()
+            .filter(|s| !s.freq_mhz.is_empty())
+            .map(|s| (s.t_secs, s.freq_mhz.iter().sum::<u64>() as f64 / s.freq_mhz.len() as f64))
+            .collect();
+        if !freq.is_empty() {
+            out.push_str("<h3>Mean core frequency</h3>");
+            out.push_str(&line_chart(&freq, "MHz", "#4a7bd0"));
+        }
+        let temp: Vec<(f64, f64)> = samples
+            .iter()
+            .filter_map(|s| s.temps_c.iter().flatten().copied().reduce(f32::max).map(|t| (s.t_secs, t as f64)))
+            .collect();
+        if !temp.is_empty() {
+            out.push_str("<h3>Hottest sensor</h3>");
+            out.push_str(&line_chart(&temp, "°C", "#d05a4a"));
+        }
+    }
+
+    out.push_str("<h2>System</h2><table>");
+    for (label, value) in system_lines(result) {
+        write!(out, "<tr><th>{}</th><td>{}</td></tr>", label, escape(&value)).unwrap();
+    }
+    out.push_str("</table></body></html>\n");
+    out
+}
@@ -1,5 +1,7 @@
 mod app;
+mod cli;
 mod engines;
+mod export;
 mod benchmarks;
 mod model;
 mod util;
@@ -7,6 +9,13 @@ mod util;
 use app::ui::OBenchmarkApp;
 
 fn main() -> eframe::Result<()> {
+    util::logging::init();
+    // sous-commandes en ligne de commande, sinon l'interface graphique
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if !args.is_empty() {
+        std::process::exit(cli::run(&args));
+    }
+
     let options = eframe::NativeOptions::default();
     eframe::run_native("OBenchmark", options, Box::new(|cc| Box::new(OBenchmarkApp::new(cc))))
 }
\ No newline at end of file
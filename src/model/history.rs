@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 use crate::model::result::BenchResult;
 
+#[allow(dead_code)]
 #[derive(Clone, Serialize, Deserialize)]
 pub struct HistoryEntry {
     pub date: String,
@@ -1,14 +1,421 @@
+use std::path::Path;
+use anyhow::{bail, Context};
 use serde::{Serialize, Deserialize};
 
+/// Secondary measurement attached to a benchmark (not used for scoring)
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Metric {
+    pub name: String,
+    pub value: f64,
+    pub unit: String,
+}
+
+/// How a benchmark's timed section was executed
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ExecMode {
+    /// Run exactly `units` work units and measure the time taken
+    FixedWork { units: u64 },
+    /// Run for `secs` seconds in auto-calibrated batches, reading the clock only between batches
+    FixedTime { secs: f64 },
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Execution {
+    pub mode: ExecMode,
+    /// Work units executed between two clock reads
+    pub batch: u64,
+    pub units: u64,
+    pub elapsed_secs: f64,
+    /// Duration of each timed batch (fixed time only), the samples behind the confidence intervals
+    #[serde(default)]
+    pub batch_secs: Vec<f64>,
+}
+
+impl Execution {
+    pub fn units_per_sec(&self) -> f64 {
+        self.units as f64 / self.elapsed_secs
+    }
+}
+
+/// Whether a larger raw score means a faster machine
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Direction {
+    #[default]
+    HigherIsBetter,
+    /// Latencies and durations
+    LowerIsBetter,
+}
+
+/// Group a benchmark is scored in; each one yields a sub-score
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Category {
+    Cpu,
+    Memory,
+    Disk,
+}
+
+impl Category {
+    pub const ALL: [Category; 3] = [Category::Cpu, Category::Memory, Category::Disk];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Category::Cpu => "CPU",
+            Category::Memory => "Memory",
+            Category::Disk => "Disk",
+        }
+    }
+}
+
+/// How normalized scores are combined into sub-scores, and sub-scores into the final score
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Aggregation {
+    /// Weighted geometric mean: one outlier cannot dominate the result
+    #[default]
+    Geometric,
+    /// Weighted arithmetic mean
+    Arithmetic,
+}
+
+impl std::str::FromStr for Aggregation {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "geometric" => Ok(Aggregation::Geometric),
+            "arithmetic" => Ok(Aggregation::Arithmetic),
+            other => anyhow::bail!("unknown aggregation {} (geometric or arithmetic)", other),
+        }
+    }
+}
+
+// results saved before sub-scores existed were a weighted arithmetic mean
+fn legacy_aggregation() -> Aggregation { Aggregation::Arithmetic }
+
+/// 95 % confidence interval of a score
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct Interval {
+    pub low: u64,
+    pub high: u64,
+}
+
+impl Interval {
+    /// The "± x" shown next to a score
+    pub fn half_width(&self) -> u64 {
+        (self.high - self.low).div_ceil(2)
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SubScore {
+    pub category: Category,
+    pub score: u64,
+    /// Valid, scored benchmarks in the category
+    pub count: usize,
+    #[serde(default)]
+    pub interval: Option<Interval>,
+}
+
 #[derive(Clone, Serialize, Deserialize)]
 pub struct BenchScore {
+    #[serde(default)]
+    pub id: String,
     pub name: String,
     pub raw_score: u64,
+    #[serde(default)]
+    pub unit: String,
+    /// `Benchmark::version` of the workload that produced `raw_score`; 0 = saved before versions existed
+    #[serde(default)]
+    pub version: u32,
     pub weight: u64,
+    /// `None` in results saved before categories existed
+    #[serde(default)]
+    pub category: Option<Category>,
+    #[serde(default)]
+    pub direction: Direction,
+    /// Raw value of the reference machine in the active profile, scored 1000; `None` = not scored
+    #[serde(default)]
+    pub reference: Option<f64>,
+    #[serde(default)]
+    pub metrics: Vec<Metric>,
+    /// False when the benchmark output failed verification; excluded from the final score
+    #[serde(default = "default_valid")]
+    pub valid: bool,
+    #[serde(default)]
+    pub execution: Option<Execution>,
+    /// When the benchmark ran, to find its part of the thermal trace
+    #[serde(default)]
+    pub span: Option<Span>,
+}
+
+/// Seconds since the start of the run
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct Span {
+    pub start_secs: f64,
+    pub end_secs: f64,
+}
+
+fn default_valid() -> bool { true }
+
+/// Runner options, recorded with the result they produced
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct RunSettings {
+    /// Cores the benchmarks and rayon workers are pinned to, one worker per core; empty = not pinned
+    #[serde(default)]
+    pub cores: Vec<usize>,
+    #[serde(default)]
+    pub high_priority: bool,
+    /// Whether the OS accepted the priority change (usually needs elevated rights)
+    #[serde(default)]
+    pub priority_raised: bool,
+}
+
+/// Load caused by other processes, sampled before the run and after each benchmark
+#[derive(Clone, Serialize, Deserialize)]
+pub struct LoadSample {
+    /// "before run", or the benchmark during which the load was measured
+    pub label: String,
+    /// CPU used by other processes, percent of the whole machine
+    pub cpu_percent: f32,
+    /// Disk reads + writes by other processes
+    pub disk_mb_s: f64,
+    pub available_memory_percent: f32,
+    /// Busiest other process
+    pub top_process: Option<String>,
+}
+
+/// CPU frequencies and temperatures sampled throughout the run
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct ThermalTrace {
+    /// Temperature sensor names, in the order of `ThermalSample::temps_c`
+    pub sensors: Vec<String>,
+    pub samples: Vec<ThermalSample>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ThermalSample {
+    /// Seconds since the start of the run
+    pub t_secs: f64,
+    /// Current frequency of each core
+    pub freq_mhz: Vec<u64>,
+    /// `None` for a sensor that could not be read (JSON has no NaN, it would come back as null)
+    #[serde(default)]
+    pub temps_c: Vec<Option<f32>>,
+}
+
+/// Machine a result was measured on
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct SystemSummary {
+    pub cpu: String,
+    pub cores: usize,
+    pub memory_mb: u64,
+    pub os: String,
 }
 
 #[derive(Clone, Serialize, Deserialize)]
 pub struct BenchResult {
     pub scores: Vec<BenchScore>,
     pub final_score: u64,
-}
\ No newline at end of file
+    /// `None` when no benchmark recorded repeated samples
+    #[serde(default)]
+    pub final_interval: Option<Interval>,
+    #[serde(default)]
+    pub sub_scores: Vec<SubScore>,
+    #[serde(default = "legacy_aggregation")]
+    pub aggregation: Aggregation,
+    /// Hardware crypto acceleration detected when the run was made
+    #[serde(default)]
+    pub cpu_features: Vec<String>,
+    #[serde(default)]
+    pub settings: RunSettings,
+    /// Other processes used significant CPU, memory or disk during the run
+    #[serde(default)]
+    pub noisy: bool,
+    #[serde(default)]
+    pub load: Vec<LoadSample>,
+    #[serde(default)]
+    pub thermal: ThermalTrace,
+    /// Id of the reference profile the scores were computed against
+    #[serde(default)]
+    pub profile: String,
+    /// `None` in results saved before it was recorded
+    #[serde(default)]
+    pub system: Option<SystemSummary>,
+    /// End of the run, RFC 3339 local time
+    #[serde(default)]
+    pub date: String,
+}
+
+impl BenchResult {
+    /// Reads a result written by the JSON export, checking that it is consistent
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let text = std::fs::read_to_string(path).with_context(|| format!("cannot read {}", path.display()))?;
+        let result: BenchResult = match serde_json::from_str(&text) {
+            Ok(result) => result,
+            // the other JSON export of the app
+            Err(_) if serde_json::from_str::<StressReport>(&text).is_ok() => {
+                bail!("{} is a stress test report, not a benchmark result", path.display())
+            }
+            Err(e) => return Err(e).with_context(|| format!("{} is not a benchmark result", path.display())),
+        };
+        result.validate().with_context(|| format!("{} is not a valid benchmark result", path.display()))?;
+        Ok(result)
+    }
+
+    // ce que serde ne vérifie pas et dont l'affichage et le scoring dépendent
+    fn validate(&self) -> anyhow::Result<()> {
+        if self.scores.is_empty() {
+            bail!("it contains no benchmark");
+        }
+        if let Some(i) = self.scores.iter().position(|s| s.name.is_empty()) {
+            bail!("benchmark #{} has no name", i + 1);
+        }
+        // the final score is clamped to five digits
+        if self.final_score > 99_999 {
+            bail!("final score {} is above 99999", self.final_score);
+        }
+        for (i, sub) in self.sub_scores.iter().enumerate() {
+            if self.sub_scores[..i].iter().any(|s| s.category == sub.category) {
+                bail!("{} sub-score appears twice", sub.category.label());
+            }
+        }
+        let intervals = self
+            .final_interval
+            .map(|i| ("final score", i))
+            .into_iter()
+            .chain(self.sub_scores.iter().filter_map(|s| s.interval.map(|i| (s.category.label(), i))));
+        for (label, interval) in intervals {
+            if interval.low > interval.high {
+                bail!("{} interval {}–{} has its bounds reversed", label, interval.low, interval.high);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Stress mode options: which workloads to loop and for how long
+#[derive(Clone, Serialize, Deserialize)]
+pub struct StressConfig {
+    pub duration_secs: u64,
+    pub cpu: bool,
+    pub memory: bool,
+    pub disk: bool,
+}
+
+impl Default for StressConfig {
+    fn default() -> Self {
+        Self { duration_secs: 600, cpu: true, memory: true, disk: false }
+    }
+}
+
+/// One pass of a stress workload
+#[derive(Clone, Serialize, Deserialize)]
+pub struct StressIteration {
+    /// Seconds since the start of the stress run, at the end of the pass
+    pub t_secs: f64,
+    pub value: u64,
+    /// The pass reproduced the expected checksum
+    pub valid: bool,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct StressWorkload {
+    pub name: String,
+    pub unit: String,
+    pub direction: Direction,
+    pub iterations: Vec<StressIteration>,
+    /// Passes that returned an error instead of a value
+    pub failures: Vec<String>,
+}
+
+impl StressWorkload {
+    pub fn errors(&self) -> usize {
+        self.iterations.iter().filter(|i| !i.valid).count() + self.failures.len()
+    }
+}
+
+/// Outcome of a stress run; replaces the score in stress mode
+#[derive(Clone, Serialize, Deserialize)]
+pub struct StressReport {
+    pub config: StressConfig,
+    pub elapsed_secs: f64,
+    /// Stopped by the user before the chosen duration
+    pub stopped: bool,
+    pub workloads: Vec<StressWorkload>,
+    pub settings: RunSettings,
+    pub thermal: ThermalTrace,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::{render, Format};
+
+    fn sample() -> BenchResult {
+        BenchResult {
+            scores: vec![BenchScore {
+                id: "cpu_int_math".to_string(),
+                name: "CPU Int Math".to_string(),
+                raw_score: 550_000_000,
+                unit: "ops/s".to_string(),
+                version: 1,
+                weight: 2,
+                category: Some(Category::Cpu),
+                direction: Direction::HigherIsBetter,
+                reference: Some(547_361_131.0),
+                metrics: Vec::new(),
+                valid: true,
+                execution: None,
+                span: Some(Span { start_secs: 0.0, end_secs: 5.0 }),
+            }],
+            final_score: 1004,
+            final_interval: Some(Interval { low: 990, high: 1020 }),
+            sub_scores: vec![SubScore { category: Category::Cpu, score: 1004, count: 1, interval: Some(Interval { low: 990, high: 1020 }) }],
+            aggregation: Aggregation::Geometric,
+            cpu_features: Vec::new(),
+            settings: RunSettings::default(),
+            noisy: false,
+            load: Vec::new(),
+            thermal: ThermalTrace {
+                sensors: vec!["cpu".to_string(), "thermal_zone0 (acpitz)".to_string()],
+                // a zone that could not be read
+                samples: vec![ThermalSample { t_secs: 1.0, freq_mhz: vec![2100], temps_c: vec![Some(45.0), None] }],
+            },
+            profile: "default-v4".to_string(),
+            system: Some(SystemSummary::default()),
+            date: String::new(),
+        }
+    }
+
+    #[test]
+    fn validate_rejects_reversed_intervals() {
+        let mut result = sample();
+        result.sub_scores[0].interval = Some(Interval { low: 1020, high: 990 });
+        assert!(result.validate().is_err());
+        let mut result = sample();
+        result.final_interval = Some(Interval { low: 1020, high: 990 });
+        assert!(result.validate().is_err());
+    }
+
+    #[test]
+    fn validate_rejects_duplicate_sub_scores() {
+        let mut result = sample();
+        result.sub_scores.push(result.sub_scores[0].clone());
+        assert!(result.validate().is_err());
+    }
+
+    #[test]
+    fn json_export_loads_back() {
+        let path = std::env::temp_dir().join(format!("obenchmark_round_trip_{}.json", std::process::id()));
+        std::fs::write(&path, render(&sample(), Format::Json)).unwrap();
+        let loaded = BenchResult::load(&path);
+        std::fs::remove_file(&path).ok();
+        let loaded = loaded.unwrap();
+        assert_eq!(loaded.final_score, 1004);
+        assert_eq!(loaded.scores[0].raw_score, 550_000_000);
+        assert_eq!(loaded.thermal.samples[0].temps_c, vec![Some(45.0), None]);
+    }
+}
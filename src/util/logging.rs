@@ -0,0 +1,102 @@
+use std::collections::VecDeque;
+use std::sync::{Mutex, OnceLock};
+use std::time::Instant;
+use log::{Level, LevelFilter, Log, Metadata, Record};
+
+// lignes gardées en mémoire pour le panneau de logs et le fichier du run
+const MAX_LINES: usize = 20_000;
+// only our own crate: eframe, winit and wgpu log through the same facade
+const TARGET_PREFIX: &str = "obenchmark";
+
+/// A formatted log line
+#[derive(Clone)]
+pub struct LogLine {
+    /// Position in the whole log, see `next_seq`
+    pub seq: u64,
+    pub level: Level,
+    pub text: String,
+}
+
+struct Logger {
+    start: Instant,
+    /// Level printed to stderr; the GUI panel and the run log always get debug lines
+    stderr_level: LevelFilter,
+    lines: Mutex<VecDeque<LogLine>>,
+    next_seq: Mutex<u64>,
+}
+
+static LOGGER: OnceLock<Logger> = OnceLock::new();
+
+/// Installs the logger; the stderr level comes from `OBENCHMARK_LOG` (error, warn, info, debug, trace)
+pub fn init() {
+    let stderr_level = std::env::var("OBENCHMARK_LOG").ok().and_then(|l| l.parse().ok()).unwrap_or(LevelFilter::Info);
+    let logger = LOGGER.get_or_init(|| Logger {
+        start: Instant::now(),
+        stderr_level,
+        lines: Mutex::new(VecDeque::new()),
+        next_seq: Mutex::new(0),
+    });
+    if log::set_logger(logger).is_ok() {
+        log::set_max_level(stderr_level.max(LevelFilter::Debug));
+    }
+}
+
+impl Log for Logger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.target().starts_with(TARGET_PREFIX)
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        // obenchmark::engines::score -> score
+        let module = record.target().rsplit("::").next().unwrap_or_default();
+        let text = format!(
+            "{:>8.3}s {:<5} [{}] {}",
+            self.start.elapsed().as_secs_f64(),
+            record.level(),
+            module,
+            record.args()
+        );
+        if record.level() <= self.stderr_level {
+            eprintln!("{}", text);
+        }
+        let seq = {
+            let mut next = self.next_seq.lock().unwrap();
+            *next += 1;
+            *next - 1
+        };
+        let mut lines = self.lines.lock().unwrap();
+        if lines.len() == MAX_LINES {
+            lines.pop_front();
+        }
+        lines.push_back(LogLine { seq, level: record.level(), text });
+    }
+
+    fn flush(&self) {}
+}
+
+/// Sequence number the next line will get; mark the start of a run with it
+pub fn next_seq() -> u64 {
+    LOGGER.get().map_or(0, |l| *l.next_seq.lock().unwrap())
+}
+
+/// Runs `f` on the kept lines, oldest first
+pub fn with_lines<R>(f: impl FnOnce(&VecDeque<LogLine>) -> R) -> R {
+    match LOGGER.get() {
+        Some(logger) => f(&logger.lines.lock().unwrap()),
+        None => f(&VecDeque::new()),
+    }
+}
+
+/// Every kept line from `seq` on, one per line, for the log file saved with a result
+pub fn text_since(seq: u64) -> String {
+    with_lines(|lines| {
+        lines
+            .iter()
+            .filter(|l| l.seq >= seq)
+            .map(|l| format!("{}\n", l.text))
+            .collect()
+    })
+}
@@ -1 +1,5 @@
-pub mod sysinfo;
\ No newline at end of file
+pub mod sysinfo;
+pub mod numa;
+pub mod priority;
+
+pub mod logging;
\ No newline at end of file
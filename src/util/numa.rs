@@ -0,0 +1,199 @@
+use std::sync::Mutex;
+use anyhow::{anyhow, bail, Result};
+
+/// A NUMA node and the CPUs attached to it
+pub struct NumaNode {
+    pub id: usize,
+    pub cpus: Vec<usize>,
+}
+
+/// NUMA topology read from `/sys/devices/system/node`; empty when unavailable (non-Linux)
+pub fn nodes() -> Vec<NumaNode> {
+    let Ok(entries) = std::fs::read_dir("/sys/devices/system/node") else {
+        return Vec::new();
+    };
+    let mut nodes: Vec<NumaNode> = entries
+        .filter_map(|e| e.ok())
+        .filter_map(|e| {
+            let id = e.file_name().to_string_lossy().strip_prefix("node")?.parse().ok()?;
+            let cpulist = std::fs::read_to_string(e.path().join("cpulist")).ok()?;
+            // memory-only nodes (CXL, HBM...) have an empty list, no CPU to run from
+            Some(NumaNode { id, cpus: parse_cpulist(&cpulist).ok()? })
+        })
+        .collect();
+    nodes.sort_by_key(|n| n.id);
+    nodes
+}
+
+/// Parses a Linux CPU list such as "0-3,8-11" into sorted, distinct CPUs
+pub fn parse_cpulist(list: &str) -> Result<Vec<usize>> {
+    if list.trim().is_empty() {
+        bail!("empty CPU list");
+    }
+    let cpu = |s: &str| s.trim().parse::<usize>().map_err(|_| anyhow!("invalid CPU list \"{}\": \"{}\" is not a CPU", list.trim(), s.trim()));
+    let mut cpus = Vec::new();
+    for part in list.split(',') {
+        match part.split_once('-') {
+            Some((a, b)) => {
+                let (a, b) = (cpu(a)?, cpu(b)?);
+                if a > b {
+                    bail!("invalid CPU list \"{}\": range {}-{} is reversed", list.trim(), a, b);
+                }
+                cpus.extend(a..=b);
+            }
+            None => cpus.push(cpu(part)?),
+        }
+    }
+    cpus.sort_unstable();
+    cpus.dedup();
+    Ok(cpus)
+}
+
+/// The reverse of `parse_cpulist`: sorted CPUs written as "0-3,8"
+pub fn format_cpulist(cpus: &[usize]) -> String {
+    let mut sorted = cpus.to_vec();
+    sorted.sort_unstable();
+    sorted.dedup();
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for cpu in sorted {
+        match ranges.last_mut() {
+            Some((_, end)) if *end + 1 == cpu => *end = cpu,
+            _ => ranges.push((cpu, cpu)),
+        }
+    }
+    ranges
+        .iter()
+        .map(|&(a, b)| if a == b { a.to_string() } else { format!("{}-{}", a, b) })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Restricts the calling thread to one CPU (`sched_setaffinity`)
+#[cfg(target_os = "linux")]
+pub fn pin_current_thread(cpu: usize) -> Result<()> {
+    // SAFETY: cpu_set_t is plain data, the set is fully initialised before the call
+    unsafe {
+        let mut set: libc::cpu_set_t = std::mem::zeroed();
+        libc::CPU_SET(cpu, &mut set);
+        if libc::sched_setaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &set) != 0 {
+            bail!("sched_setaffinity(cpu {}): {}", cpu, std::io::Error::last_os_error());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn pin_current_thread(_cpu: usize) -> Result<()> {
+    bail!("thread pinning is only supported on Linux")
+}
+
+/// Binds the pages of `buf` to `node` (`mbind`, MPOL_BIND), moving pages already touched.
+/// Call it before the buffer is written so the pages are allocated on the node directly.
+#[cfg(target_os = "linux")]
+pub fn bind_to_node<T>(buf: &[T], node: usize) -> Result<()> {
+    const MPOL_BIND: libc::c_long = 2;
+    const MPOL_MF_MOVE: libc::c_long = 1 << 1;
+    const MASK_BITS: usize = 1024;
+
+    if buf.is_empty() {
+        return Ok(());
+    }
+    if node >= MASK_BITS {
+        bail!("NUMA node {} out of range", node);
+    }
+    // mbind wants a page-aligned range
+    // SAFETY: sysconf has no preconditions
+    let page = unsafe { libc::sysconf(libc::_SC_PAGESIZE) }.max(4096) as usize;
+    let start = buf.as_ptr() as usize & !(page - 1);
+    let end = buf.as_ptr() as usize + std::mem::size_of_val(buf);
+    let mut mask = [0u64; MASK_BITS / 64];
+    mask[node / 64] |= 1 << (node % 64);
+    // SAFETY: the range covers memory owned by `buf`, mbind does not change its contents
+    let ret = unsafe {
+        libc::syscall(
+            libc::SYS_mbind,
+            start,
+            end - start,
+            MPOL_BIND,
+            mask.as_ptr(),
+            MASK_BITS as libc::c_ulong,
+            MPOL_MF_MOVE,
+        )
+    };
+    if ret != 0 {
+        bail!("mbind(node {}): {}", node, std::io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn bind_to_node<T>(_buf: &[T], _node: usize) -> Result<()> {
+    bail!("NUMA memory binding is only supported on Linux")
+}
+
+/// Rayon pool with one thread pinned to each of `cpus`; fails if any of them could not be pinned
+pub fn pinned_pool(cpus: &[usize]) -> Result<rayon::ThreadPool> {
+    let pool = rayon::ThreadPoolBuilder::new().num_threads(cpus.len()).build()?;
+    // broadcast runs once on every worker, so each pinning error comes back here
+    pool.broadcast(|ctx| pin_current_thread(cpus[ctx.index()])).into_iter().collect::<Result<Vec<()>>>()?;
+    Ok(pool)
+}
+
+/// Every CPU the OS reports, in node order
+pub fn all_cpus() -> Vec<usize> {
+    let cpus: Vec<usize> = nodes().into_iter().flat_map(|n| n.cpus).collect();
+    if cpus.is_empty() { (0..num_cpus::get()).collect() } else { cpus }
+}
+
+// cores chosen for the current run, empty when it is not pinned
+static RUN_CPUS: Mutex<Vec<usize>> = Mutex::new(Vec::new());
+
+/// Limits `usable_cpus()` to `cores` until the next call; an empty list lifts the limit
+pub fn restrict_cpus(cores: &[usize]) {
+    *RUN_CPUS.lock().unwrap_or_else(|e| e.into_inner()) = cores.to_vec();
+}
+
+/// The CPUs a benchmark that pins its own threads may use: the run's cores, else every CPU
+pub fn usable_cpus() -> Vec<usize> {
+    let chosen = RUN_CPUS.lock().unwrap_or_else(|e| e.into_inner()).clone();
+    let cpus = all_cpus();
+    if chosen.is_empty() { cpus } else { cpus.into_iter().filter(|c| chosen.contains(c)).collect() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cpulist_round_trip() {
+        assert_eq!(parse_cpulist("0-3,8").unwrap(), vec![0, 1, 2, 3, 8]);
+        assert_eq!(format_cpulist(&parse_cpulist("0-3,8").unwrap()), "0-3,8");
+        assert_eq!(format_cpulist(&parse_cpulist("10-11,0-1,5").unwrap()), "0-1,5,10-11");
+        // sysfs ends the list with a newline
+        assert_eq!(parse_cpulist("0-1\n").unwrap(), vec![0, 1]);
+    }
+
+    #[test]
+    fn cpulist_duplicates_and_whitespace() {
+        assert_eq!(parse_cpulist("1,1,0-2").unwrap(), vec![0, 1, 2]);
+        assert_eq!(parse_cpulist(" 0 - 1 , 4 ").unwrap(), vec![0, 1, 4]);
+        assert_eq!(format_cpulist(&[3, 1, 2, 2]), "1-3");
+    }
+
+    #[test]
+    fn usable_cpus_follow_the_run_cores() {
+        let all = all_cpus();
+        // a core the machine does not have is never handed out
+        restrict_cpus(&[all[0], usize::MAX]);
+        assert_eq!(usable_cpus(), vec![all[0]]);
+        restrict_cpus(&[]);
+        assert_eq!(usable_cpus(), all);
+    }
+
+    #[test]
+    fn cpulist_rejects_malformed() {
+        for list in ["", " ", "a", "3-1", "1-", "-1", "0,,2", "0-a"] {
+            assert!(parse_cpulist(list).is_err(), "{:?}", list);
+        }
+    }
+}
@@ -1,7 +1,62 @@
 use sysinfo::System;
+use crate::model::result::SystemSummary;
 
 pub fn get_system_info() -> System {
     let mut sys = System::new_all();
     sys.refresh_all();
     sys
-}
\ No newline at end of file
+}
+
+/// CPU, memory and OS of this machine, stored with each result
+pub fn system_summary() -> SystemSummary {
+    let sys = get_system_info();
+    SystemSummary {
+        // the global entry has no brand, every core has the same one
+        cpu: sys.cpus().first().map(|c| c.brand().trim().to_string()).unwrap_or_default(),
+        cores: sys.cpus().len(),
+        memory_mb: sys.total_memory() / (1024 * 1024),
+        os: System::long_os_version().unwrap_or_else(|| "unknown".to_string()),
+    }
+}
+
+/// Hardware crypto acceleration available on this CPU (AES-NI, SHA extensions...)
+pub fn crypto_features() -> Vec<String> {
+    let mut features = Vec::new();
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        if std::arch::is_x86_feature_detected!("aes") { features.push("aes-ni".to_string()); }
+        if std::arch::is_x86_feature_detected!("pclmulqdq") { features.push("pclmulqdq".to_string()); }
+        if std::arch::is_x86_feature_detected!("sha") { features.push("sha-ni".to_string()); }
+        if std::arch::is_x86_feature_detected!("avx2") { features.push("avx2".to_string()); }
+    }
+    #[cfg(target_arch = "aarch64")]
+    {
+        if std::arch::is_aarch64_feature_detected!("aes") { features.push("aes".to_string()); }
+        if std::arch::is_aarch64_feature_detected!("pmull") { features.push("pmull".to_string()); }
+        if std::arch::is_aarch64_feature_detected!("sha2") { features.push("sha2".to_string()); }
+        if std::arch::is_aarch64_feature_detected!("sha3") { features.push("sha3".to_string()); }
+    }
+    features
+}
+
+/// Size in bytes of the largest CPU cache, read from the Linux `/sys` topology
+pub fn last_level_cache_bytes() -> Option<usize> {
+    let entries = std::fs::read_dir("/sys/devices/system/cpu/cpu0/cache").ok()?;
+    entries
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_name().to_string_lossy().starts_with("index"))
+        .filter_map(|e| std::fs::read_to_string(e.path().join("size")).ok())
+        .filter_map(|size| parse_cache_size(size.trim()))
+        .max()
+}
+
+// "48K", "2048K", "32M"
+fn parse_cache_size(size: &str) -> Option<usize> {
+    let (digits, multiplier) = match size.chars().last()? {
+        'K' => (&size[..size.len() - 1], 1024),
+        'M' => (&size[..size.len() - 1], 1024 * 1024),
+        'G' => (&size[..size.len() - 1], 1024 * 1024 * 1024),
+        _ => (size, 1),
+    };
+    digits.parse::<usize>().ok().map(|n| n * multiplier)
+}